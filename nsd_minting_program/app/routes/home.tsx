@@ -44,7 +44,7 @@ export default function Home() {
     if (!publicKey) return;
     
     try {
-      await mintTokens(parseInt(mintAmount));
+      await mintTokens(new PublicKey(tokenMint), parseInt(mintAmount));
     } catch (err) {
       console.error('Failed to mint tokens:', err);
     }
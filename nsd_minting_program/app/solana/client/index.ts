@@ -1,2 +1,2 @@
 export * from "./pda"
-export * from "./rpc"
\ No newline at end of file
+export * from "./rpc"
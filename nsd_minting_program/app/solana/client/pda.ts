@@ -1,10 +1,88 @@
 import {PublicKey} from "@solana/web3.js";
 import BN from "bn.js";
 
-export const deriveNsdConfigPDA = (programId: PublicKey): [PublicKey, number] => {
+export type NsdConfigSeeds = {
+    tokenMint: PublicKey, 
+};
+
+export const deriveNsdConfigPDA = (
+    seeds: NsdConfigSeeds,
+    programId: PublicKey
+): [PublicKey, number] => {
     return PublicKey.findProgramAddressSync(
         [
             Buffer.from("nsd_config"),
+            seeds.tokenMint.toBuffer(),
+        ],
+        programId,
+    )
+};
+
+export type NsdTreasurySeeds = {
+    config: PublicKey, 
+};
+
+export const deriveNsdTreasuryPDA = (
+    seeds: NsdTreasurySeeds,
+    programId: PublicKey
+): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("treasury"),
+            seeds.config.toBuffer(),
+        ],
+        programId,
+    )
+};
+
+export type NsdMintAuthoritySeeds = {
+    config: PublicKey, 
+};
+
+export const deriveNsdMintAuthorityPDA = (
+    seeds: NsdMintAuthoritySeeds,
+    programId: PublicKey
+): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("mint_authority"),
+            seeds.config.toBuffer(),
+        ],
+        programId,
+    )
+};
+
+export type NsdFreezeAuthoritySeeds = {
+    config: PublicKey, 
+};
+
+export const deriveNsdFreezeAuthorityPDA = (
+    seeds: NsdFreezeAuthoritySeeds,
+    programId: PublicKey
+): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("freeze_authority"),
+            seeds.config.toBuffer(),
+        ],
+        programId,
+    )
+};
+
+export type SalePhaseSeeds = {
+    config: PublicKey, 
+    phaseId: number, 
+};
+
+export const deriveSalePhasePDA = (
+    seeds: SalePhaseSeeds,
+    programId: PublicKey
+): [PublicKey, number] => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("sale_phase"),
+            seeds.config.toBuffer(),
+            Buffer.from([seeds.phaseId]),
         ],
         programId,
     )
@@ -28,6 +106,7 @@ export const deriveNsdTokenPDA = (
 };
 
 export type NsdUserSeeds = {
+    config: PublicKey, 
     user: PublicKey, 
 };
 
@@ -38,6 +117,7 @@ export const deriveNsdUserPDA = (
     return PublicKey.findProgramAddressSync(
         [
             Buffer.from("nsd_user"),
+            seeds.config.toBuffer(),
             seeds.user.toBuffer(),
         ],
         programId,
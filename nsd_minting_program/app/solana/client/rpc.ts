@@ -132,13 +132,12 @@ export const initializeConfigSendAndConfirm = async (
 
 export type MintTokensArgs = {
   feePayer: web3.PublicKey;
-  userTokenAccount: web3.PublicKey;
   user: web3.PublicKey;
   tokenMint: web3.PublicKey;
-  mint: web3.PublicKey;
-  owner: web3.PublicKey;
-  wallet: web3.PublicKey;
+  tokenProgram: web3.PublicKey;
+  salePhaseId: number | undefined;
   mintAmount: bigint;
+  maxTotalCost: bigint;
 };
 
 /**
@@ -148,54 +147,73 @@ export type MintTokensArgs = {
  * Accounts:
  * 0. `[writable, signer]` fee_payer: {@link PublicKey} 
  * 1. `[writable]` config: {@link NsdConfig} 
- * 2. `[writable]` user_token_account: {@link PublicKey} User's token account
- * 3. `[signer]` user: {@link PublicKey} User's wallet address
- * 4. `[writable]` user_account: {@link NsdUser} 
- * 5. `[]` token_mint: {@link Mint} NSD token mint account
- * 6. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
- * 7. `[writable]` mint: {@link Mint} The mint.
- * 8. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to.
- * 9. `[signer]` owner: {@link PublicKey} The mint's minting authority.
- * 10. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
- * 11. `[]` token_program: {@link PublicKey} SPL Token program
- * 12. `[]` token_program: {@link PublicKey} Auto-generated, TokenProgram
+ * 2. `[writable]` treasury: {@link NsdTreasury} Treasury receiving the mint price
+ * 3. `[writable]` user_token_account: {@link PublicKey} Ignored, kept so the later account indices don't shift
+ * 4. `[signer]` user: {@link PublicKey} User's wallet address
+ * 5. `[writable]` user_account: {@link NsdUser} 
+ * 6. `[]` token_mint: {@link Mint} NSD token mint account
+ * 7. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
+ * 8. `[writable]` mint: {@link Mint} The mint.
+ * 9. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to, created if needed.
+ * 10. `[]` mint_authority: {@link PublicKey} Program PDA holding the mint authority
+ * 11. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
+ * 12. `[]` token_program: {@link PublicKey} Token program, SPL Token or Token-2022
+ * 13. `[]` associated_token_program: {@link PublicKey} Associated Token program
+ * 14. `[]` sale_phase: {@link SalePhase} Optional, the active sale phase once phases are configured
  *
  * Data:
  * - mint_amount: {@link BigInt} Number of tokens to mint
+ * - max_total_cost: {@link BigInt} Most lamports the buyer accepts paying
  */
 export const mintTokensBuilder = (
 	args: MintTokensArgs,
 	remainingAccounts: Array<web3.AccountMeta> = [],
 ): MethodsBuilder<NsdMinting, never> => {
-  const [configPubkey] = pda.deriveNsdConfigPDA(_program.programId);
+  const [configPubkey] = pda.deriveNsdConfigPDA({
+        tokenMint: args.tokenMint,
+    }, _program.programId);
+    const [treasuryPubkey] = pda.deriveNsdTreasuryPDA({
+        config: configPubkey,
+    }, _program.programId);
     const [userAccountPubkey] = pda.deriveNsdUserPDA({
+        config: configPubkey,
         user: args.user,
     }, _program.programId);
-    const [assocTokenAccountPubkey] = pda.CslSplTokenPDAs.deriveAccountPDA({
-        wallet: args.wallet,
-        tokenProgram: new web3.PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
-        mint: args.mint,
+    const [mintAuthorityPubkey] = pda.deriveNsdMintAuthorityPDA({
+        config: configPubkey,
+    }, _program.programId);
+    const [assocTokenAccountPubkey] = pda.TokenProgramPDAs.deriveAccountPDA({
+        wallet: args.user,
+        tokenProgram: args.tokenProgram,
+        mint: args.tokenMint,
     }, new web3.PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"));
+    const salePhasePubkey = args.salePhaseId === undefined ? null : pda.deriveSalePhasePDA({
+        config: configPubkey,
+        phaseId: args.salePhaseId,
+    }, _program.programId)[0];
 
   return _program
     .methods
     .mintTokens(
       new BN(args.mintAmount.toString()),
+      new BN(args.maxTotalCost.toString()),
     )
     .accountsStrict({
       feePayer: args.feePayer,
       config: configPubkey,
-      userTokenAccount: args.userTokenAccount,
+      treasury: treasuryPubkey,
+      userTokenAccount: assocTokenAccountPubkey,
       user: args.user,
       userAccount: userAccountPubkey,
       tokenMint: args.tokenMint,
       systemProgram: new web3.PublicKey("11111111111111111111111111111111"),
-      mint: args.mint,
+      mint: args.tokenMint,
       assocTokenAccount: assocTokenAccountPubkey,
-      owner: args.owner,
-      wallet: args.wallet,
-      tokenProgram: new web3.PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
-      tokenProgram: new web3.PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+      mintAuthority: mintAuthorityPubkey,
+      wallet: args.user,
+      tokenProgram: args.tokenProgram,
+      associatedTokenProgram: new web3.PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+      salePhase: salePhasePubkey,
     })
     .remainingAccounts(remainingAccounts);
 };
@@ -207,20 +225,23 @@ export const mintTokensBuilder = (
  * Accounts:
  * 0. `[writable, signer]` fee_payer: {@link PublicKey} 
  * 1. `[writable]` config: {@link NsdConfig} 
- * 2. `[writable]` user_token_account: {@link PublicKey} User's token account
- * 3. `[signer]` user: {@link PublicKey} User's wallet address
- * 4. `[writable]` user_account: {@link NsdUser} 
- * 5. `[]` token_mint: {@link Mint} NSD token mint account
- * 6. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
- * 7. `[writable]` mint: {@link Mint} The mint.
- * 8. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to.
- * 9. `[signer]` owner: {@link PublicKey} The mint's minting authority.
- * 10. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
- * 11. `[]` token_program: {@link PublicKey} SPL Token program
- * 12. `[]` token_program: {@link PublicKey} Auto-generated, TokenProgram
+ * 2. `[writable]` treasury: {@link NsdTreasury} Treasury receiving the mint price
+ * 3. `[writable]` user_token_account: {@link PublicKey} Ignored, kept so the later account indices don't shift
+ * 4. `[signer]` user: {@link PublicKey} User's wallet address
+ * 5. `[writable]` user_account: {@link NsdUser} 
+ * 6. `[]` token_mint: {@link Mint} NSD token mint account
+ * 7. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
+ * 8. `[writable]` mint: {@link Mint} The mint.
+ * 9. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to, created if needed.
+ * 10. `[]` mint_authority: {@link PublicKey} Program PDA holding the mint authority
+ * 11. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
+ * 12. `[]` token_program: {@link PublicKey} Token program, SPL Token or Token-2022
+ * 13. `[]` associated_token_program: {@link PublicKey} Associated Token program
+ * 14. `[]` sale_phase: {@link SalePhase} Optional, the active sale phase once phases are configured
  *
  * Data:
  * - mint_amount: {@link BigInt} Number of tokens to mint
+ * - max_total_cost: {@link BigInt} Most lamports the buyer accepts paying
  */
 export const mintTokens = (
 	args: MintTokensArgs,
@@ -228,6 +249,28 @@ export const mintTokens = (
 ): Promise<web3.TransactionInstruction> =>
     mintTokensBuilder(args, remainingAccounts).instruction();
 
+/**
+ * Quote a mint by simulating the `quote_mint` view instruction
+ *
+ * The program prices the mint with the same code and clock as the mint itself,
+ * so the quote cannot drift from it. Use the result as `maxTotalCost`.
+ */
+export const quoteMintTokens = async (
+  args: Pick<MintTokensArgs, "tokenMint" | "salePhaseId" | "mintAmount">,
+): Promise<bigint> => {
+  const [configPubkey] = pda.deriveNsdConfigPDA({ tokenMint: args.tokenMint }, _program.programId);
+  const salePhase = args.salePhaseId === undefined ? null : pda.deriveSalePhasePDA(
+    { config: configPubkey, phaseId: args.salePhaseId },
+    _program.programId,
+  )[0];
+  const cost: BN = await _program
+    .methods
+    .quoteMint(new BN(args.mintAmount.toString()))
+    .accountsPartial({ config: configPubkey, salePhase })
+    .view();
+  return BigInt(cost.toString());
+};
+
 /**
  * ### Returns a {@link web3.TransactionSignature}
  * Mint NSD tokens for a user
@@ -235,42 +278,45 @@ export const mintTokens = (
  * Accounts:
  * 0. `[writable, signer]` fee_payer: {@link PublicKey} 
  * 1. `[writable]` config: {@link NsdConfig} 
- * 2. `[writable]` user_token_account: {@link PublicKey} User's token account
- * 3. `[signer]` user: {@link PublicKey} User's wallet address
- * 4. `[writable]` user_account: {@link NsdUser} 
- * 5. `[]` token_mint: {@link Mint} NSD token mint account
- * 6. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
- * 7. `[writable]` mint: {@link Mint} The mint.
- * 8. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to.
- * 9. `[signer]` owner: {@link PublicKey} The mint's minting authority.
- * 10. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
- * 11. `[]` token_program: {@link PublicKey} SPL Token program
- * 12. `[]` token_program: {@link PublicKey} Auto-generated, TokenProgram
+ * 2. `[writable]` treasury: {@link NsdTreasury} Treasury receiving the mint price
+ * 3. `[writable]` user_token_account: {@link PublicKey} Ignored, kept so the later account indices don't shift
+ * 4. `[signer]` user: {@link PublicKey} User's wallet address
+ * 5. `[writable]` user_account: {@link NsdUser} 
+ * 6. `[]` token_mint: {@link Mint} NSD token mint account
+ * 7. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
+ * 8. `[writable]` mint: {@link Mint} The mint.
+ * 9. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to, created if needed.
+ * 10. `[]` mint_authority: {@link PublicKey} Program PDA holding the mint authority
+ * 11. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
+ * 12. `[]` token_program: {@link PublicKey} Token program, SPL Token or Token-2022
+ * 13. `[]` associated_token_program: {@link PublicKey} Associated Token program
+ * 14. `[]` sale_phase: {@link SalePhase} Optional, the active sale phase once phases are configured
  *
  * Data:
  * - mint_amount: {@link BigInt} Number of tokens to mint
+ * - max_total_cost: {@link BigInt} Most lamports the buyer accepts paying, quoted fresh with {@link quoteMintTokens} if omitted
  */
 export const mintTokensSendAndConfirm = async (
-  args: Omit<MintTokensArgs, "feePayer" | "user" | "owner"> & {
+  args: Omit<MintTokensArgs, "feePayer" | "user" | "maxTotalCost"> & {
+    maxTotalCost?: bigint,
     signers: {
       feePayer: web3.Signer,
       user: web3.Signer,
-      owner: web3.Signer,
     },
   },
   remainingAccounts: Array<web3.AccountMeta> = [],
 ): Promise<web3.TransactionSignature> => {
   const preInstructions: Array<web3.TransactionInstruction> = [];
-
+  const maxTotalCost = args.maxTotalCost ?? await quoteMintTokens(args);
 
   return mintTokensBuilder({
       ...args,
+      maxTotalCost,
       feePayer: args.signers.feePayer.publicKey,
       user: args.signers.user.publicKey,
-      owner: args.signers.owner.publicKey,
     }, remainingAccounts)
     .preInstructions(preInstructions)
-    .signers([args.signers.feePayer, args.signers.user, args.signers.owner])
+    .signers([args.signers.feePayer, args.signers.user])
     .rpc();
 }
 
@@ -509,3 +555,8 @@ export const getNsdTokenMetadata = (
     publicKey: web3.PublicKey,
     commitment?: web3.Commitment
 ): Promise<IdlAccounts<NsdMinting>["nsdTokenMetadata"]> => _program.account.nsdTokenMetadata.fetch(publicKey, commitment);
+
+export const getSalePhase = (
+    publicKey: web3.PublicKey,
+    commitment?: web3.Commitment
+): Promise<IdlAccounts<NsdMinting>["salePhase"]> => _program.account.salePhase.fetch(publicKey, commitment);
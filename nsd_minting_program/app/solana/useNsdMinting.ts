@@ -17,6 +17,9 @@ import { PublicKey, Transaction, SystemProgram, Keypair } from '@solana/web3.js'
 import { useEffect, useState } from 'react';
 import { useProgram } from './useProgram';
 
+// SPL Token program, owner of mints created by initialize_sale
+const TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
+
 /**
  * Custom hook for NSD Minting program interactions
  * 
@@ -28,7 +31,7 @@ export const useNsdMinting = () => {
   const { connection } = useConnection();
   const { publicKey, sendTransaction } = useWallet();
   // Get program instance and ID from useProgram hook
-  const { program, programId } = useProgram();
+  const { program, programId, mintTokens: buildMintTokens, quoteMintTokens } = useProgram();
   // State management for loading and error states
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -92,39 +95,44 @@ export const useNsdMinting = () => {
    * Mint NSD tokens for a user
    * 
    * This function mints NSD tokens to the connected user's associated token account.
-   * It performs validation checks before minting and updates tracking information.
+   * It quotes the mint against freshly fetched accounts and passes the quote as the
+   * maximum total cost, so the mint fails instead of charging more if the price rises
+   * before the transaction lands.
    * 
+   * @param tokenMint - The mint address for NSD token
    * @param mintAmount - Number of tokens to mint
    * @returns Promise resolving to the transaction signature or undefined
    */
   const mintTokens = async (
+    tokenMint: PublicKey,
     mintAmount: number
   ) => {
-    // Check if wallet is connected and program is available
-    if (!publicKey || !program) return;
+    // Check if wallet is connected
+    if (!publicKey) return;
     
     // Set loading state
     setLoading(true);
     setError(null);
     
     try {
-      // Derive the configuration PDA address using seeds
-      const [configPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('nsd_config')],
-        programId
-      );
+      // Quote the mint at the current price
+      const maxTotalCost = await quoteMintTokens({
+        tokenMint,
+        salePhaseId: undefined,
+        mintAmount: BigInt(mintAmount),
+      });
 
       // Build and send the transaction
-      const tx = await program.methods
-        .mintTokens(mintAmount)
-        .accounts({
-          feePayer: publicKey,
-          config: configPda,
-          user: publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([])
-        .rpc();
+      const instruction = await buildMintTokens({
+        feePayer: publicKey,
+        user: publicKey,
+        tokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        salePhaseId: undefined,
+        mintAmount: BigInt(mintAmount),
+        maxTotalCost,
+      });
+      const tx = await sendTransaction(new Transaction().add(instruction), connection);
 
       // Log successful transaction
       console.log('Mint tokens transaction:', tx);
@@ -139,20 +139,23 @@ const useProgram = (props?: UseProgramProps | undefined) => {
    * Accounts:
    * 0. `[writable, signer]` fee_payer: {@link PublicKey} 
    * 1. `[writable]` config: {@link NsdConfig} 
-   * 2. `[writable]` user_token_account: {@link PublicKey} User's token account
-   * 3. `[signer]` user: {@link PublicKey} User's wallet address
-   * 4. `[writable]` user_account: {@link NsdUser} 
-   * 5. `[]` token_mint: {@link Mint} NSD token mint account
-   * 6. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
-   * 7. `[writable]` mint: {@link Mint} The mint.
-   * 8. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to.
-   * 9. `[signer]` owner: {@link PublicKey} The mint's minting authority.
-   * 10. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
-   * 11. `[]` token_program: {@link PublicKey} SPL Token program
-   * 12. `[]` token_program: {@link PublicKey} Auto-generated, TokenProgram
+   * 2. `[writable]` treasury: {@link NsdTreasury} Treasury receiving the mint price
+   * 3. `[writable]` user_token_account: {@link PublicKey} Ignored, kept so the later account indices don't shift
+   * 4. `[signer]` user: {@link PublicKey} User's wallet address
+   * 5. `[writable]` user_account: {@link NsdUser} 
+   * 6. `[]` token_mint: {@link Mint} NSD token mint account
+   * 7. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
+   * 8. `[writable]` mint: {@link Mint} The mint.
+   * 9. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to, created if needed.
+   * 10. `[]` mint_authority: {@link PublicKey} Program PDA holding the mint authority
+   * 11. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
+   * 12. `[]` token_program: {@link PublicKey} Token program, SPL Token or Token-2022
+   * 13. `[]` associated_token_program: {@link PublicKey} Associated Token program
+   * 14. `[]` sale_phase: {@link SalePhase} Optional, the active sale phase once phases are configured
    *
    * Data:
    * - mint_amount: {@link BigInt} Number of tokens to mint
+   * - max_total_cost: {@link BigInt} Most lamports the buyer accepts paying
    *
    * @returns {@link TransactionInstruction}
    */
@@ -164,33 +167,43 @@ const useProgram = (props?: UseProgramProps | undefined) => {
    * Accounts:
    * 0. `[writable, signer]` fee_payer: {@link PublicKey} 
    * 1. `[writable]` config: {@link NsdConfig} 
-   * 2. `[writable]` user_token_account: {@link PublicKey} User's token account
-   * 3. `[signer]` user: {@link PublicKey} User's wallet address
-   * 4. `[writable]` user_account: {@link NsdUser} 
-   * 5. `[]` token_mint: {@link Mint} NSD token mint account
-   * 6. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
-   * 7. `[writable]` mint: {@link Mint} The mint.
-   * 8. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to.
-   * 9. `[signer]` owner: {@link PublicKey} The mint's minting authority.
-   * 10. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
-   * 11. `[]` token_program: {@link PublicKey} SPL Token program
-   * 12. `[]` token_program: {@link PublicKey} Auto-generated, TokenProgram
+   * 2. `[writable]` treasury: {@link NsdTreasury} Treasury receiving the mint price
+   * 3. `[writable]` user_token_account: {@link PublicKey} Ignored, kept so the later account indices don't shift
+   * 4. `[signer]` user: {@link PublicKey} User's wallet address
+   * 5. `[writable]` user_account: {@link NsdUser} 
+   * 6. `[]` token_mint: {@link Mint} NSD token mint account
+   * 7. `[]` system_program: {@link PublicKey} Auto-generated, for account initialization
+   * 8. `[writable]` mint: {@link Mint} The mint.
+   * 9. `[writable]` assoc_token_account: {@link Account} The account to mint tokens to, created if needed.
+   * 10. `[]` mint_authority: {@link PublicKey} Program PDA holding the mint authority
+   * 11. `[]` wallet: {@link PublicKey} Wallet address for the new associated token account
+   * 12. `[]` token_program: {@link PublicKey} Token program, SPL Token or Token-2022
+   * 13. `[]` associated_token_program: {@link PublicKey} Associated Token program
+   * 14. `[]` sale_phase: {@link SalePhase} Optional, the active sale phase once phases are configured
    *
    * Data:
    * - mint_amount: {@link BigInt} Number of tokens to mint
+   * - max_total_cost: {@link BigInt} Most lamports the buyer accepts paying
    *
    * @returns {@link SendAndConfirmTxResult}
    */
   const mintTokensSendAndConfirm = useCallback(async (
-    args: Omit<programClient.MintTokensArgs, "feePayer" | "user" | "owner"> & {
+    args: Omit<programClient.MintTokensArgs, "feePayer" | "user" | "maxTotalCost"> & {
+    maxTotalCost?: bigint,
     signers: {
         feePayer: Keypair,
         user: Keypair,
-        owner: Keypair,
     }}, 
     remainingAccounts: Array<AccountMeta> = []
   ): Promise<SendAndConfirmTxResult> => sendAndConfirmTx(() => programClient.mintTokensSendAndConfirm(args, remainingAccounts)), [])
 
+  /**
+   * Quote a mint against freshly fetched accounts, for use as `maxTotalCost`
+   *
+   * @returns Total cost in lamports
+   */
+  const quoteMintTokens = useCallback(programClient.quoteMintTokens, [])
+
   /**
    * Update NSD minting configuration
    *
@@ -284,6 +297,7 @@ const useProgram = (props?: UseProgramProps | undefined) => {
   const getNsdToken = useCallback(programClient.getNsdToken, [])
   const getNsdUser = useCallback(programClient.getNsdUser, [])
   const getNsdTokenMetadata = useCallback(programClient.getNsdTokenMetadata, [])
+  const getSalePhase = useCallback(programClient.getSalePhase, [])
 
   const deriveNsdConfig = useCallback(programClient.deriveNsdConfigPDA,[])
   const deriveNsdToken = useCallback(programClient.deriveNsdTokenPDA,[])
@@ -297,6 +311,7 @@ const useProgram = (props?: UseProgramProps | undefined) => {
     initializeConfigSendAndConfirm,
     mintTokens,
     mintTokensSendAndConfirm,
+    quoteMintTokens,
     updateConfig,
     updateConfigSendAndConfirm,
     setTokenMetadata,
@@ -305,6 +320,7 @@ const useProgram = (props?: UseProgramProps | undefined) => {
     getNsdToken,
     getNsdUser,
     getNsdTokenMetadata,
+    getSalePhase,
     deriveNsdConfig,
     deriveNsdToken,
     deriveNsdUser,
@@ -0,0 +1,267 @@
+//! NSD Admin - command-line tool for the NSD Minting Program
+//!
+//! One function per subcommand. Each builds its instructions with the client crate,
+//! names the accounts it changes so `--simulate` can diff them, and runs them through the session.
+
+// Import required modules and macros
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{bail, Result};
+use nsd_minting_client::{instructions, pda, BondingCurve, DutchAuction, NsdConfig, NsdUser, UpdateConfigArgs};
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::display::{self, AccountKind, Watched};
+use crate::session::{read_keypair, Session};
+use crate::{
+    FinalizeArgs, InitArgs, SaleArgs, SetBondingCurveArgs, SetDutchAuctionArgs, SetMetadataArgs, SetPaymentMintsArgs, SetPriceTiersArgs,
+    ShowUserArgs, WithdrawArgs, WithdrawTokensArgs,
+};
+
+pub fn init(session: &Session, args: InitArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = match &args.mint_keypair {
+        Some(path) => read_keypair(path)?,
+        None => Keypair::new(),
+    };
+    let token_program = if args.token_2022 { anchor_spl::token_2022::ID } else { anchor_spl::token::ID };
+    let (config, _) = pda::config_address(&token_mint.pubkey());
+
+    println!("Token mint: {}", token_mint.pubkey());
+    let instruction = instructions::initialize_sale(
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &token_mint.pubkey(),
+        &token_program,
+        args.decimals,
+        args.max_supply,
+        args.price,
+        args.min_price,
+        args.max_price,
+    );
+    session.execute(
+        &payer,
+        &[instruction],
+        &[&token_mint],
+        &[
+            Watched::new("config", config, AccountKind::Config),
+            Watched::new("treasury", pda::treasury_address(&config).0, AccountKind::Lamports),
+        ],
+    )
+}
+
+pub fn update_config(session: &Session, args: crate::UpdateConfigArgs) -> Result<()> {
+    if args.max_supply.is_none() && args.price.is_none() && args.active.is_none() {
+        bail!("nothing to update, pass --max-supply, --price or --active");
+    }
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+    let update = UpdateConfigArgs {
+        max_supply: args.max_supply,
+        mint_price: args.price,
+        is_active: args.active,
+        ..Default::default()
+    };
+
+    let instruction = instructions::update_config(&payer.pubkey(), &payer.pubkey(), &token_mint, update);
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[Watched::new("config", pda::config_address(&token_mint).0, AccountKind::Config)],
+    )
+}
+
+pub fn freeze_max_supply(session: &Session, args: SaleArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.mint;
+
+    let instruction = instructions::freeze_max_supply(&payer.pubkey(), &payer.pubkey(), &token_mint);
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[Watched::new("config", pda::config_address(&token_mint).0, AccountKind::Config)],
+    )
+}
+
+pub fn set_price_tiers(session: &Session, args: SetPriceTiersArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+
+    let instruction = instructions::set_price_tiers(&payer.pubkey(), &payer.pubkey(), &token_mint, args.tiers);
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[Watched::new("config", pda::config_address(&token_mint).0, AccountKind::Config)],
+    )
+}
+
+pub fn set_bonding_curve(session: &Session, args: SetBondingCurveArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+    let curve = match (args.base_price, args.slope) {
+        (Some(base_price), Some(slope)) => Some(BondingCurve { base_price, slope }),
+        _ => None,
+    };
+
+    let instruction = instructions::set_bonding_curve(&payer.pubkey(), &payer.pubkey(), &token_mint, curve);
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[Watched::new("config", pda::config_address(&token_mint).0, AccountKind::Config)],
+    )
+}
+
+pub fn set_dutch_auction(session: &Session, args: SetDutchAuctionArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+    let auction = match (args.start_price, args.floor_price, args.start, args.decay_interval) {
+        (Some(start_price), Some(floor_price), Some(start_timestamp), Some(decay_interval)) => {
+            Some(DutchAuction { start_price, floor_price, start_timestamp, decay_interval })
+        }
+        _ => None,
+    };
+
+    let instruction = instructions::set_dutch_auction(&payer.pubkey(), &payer.pubkey(), &token_mint, auction);
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[Watched::new("config", pda::config_address(&token_mint).0, AccountKind::Config)],
+    )
+}
+
+pub fn set_payment_mints(session: &Session, args: SetPaymentMintsArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+
+    let instruction = instructions::set_payment_mints(&payer.pubkey(), &payer.pubkey(), &token_mint, args.payment_mints);
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[Watched::new("config", pda::config_address(&token_mint).0, AccountKind::Config)],
+    )
+}
+
+pub fn set_metadata(session: &Session, args: SetMetadataArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+    // The mint's owner tells SPL Token mints, which get Metaplex metadata, from Token-2022 mints
+    let Some(mint_account) = session.snapshot(&token_mint)? else {
+        bail!("token mint {token_mint} does not exist");
+    };
+
+    let instruction = instructions::set_token_metadata(
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &token_mint,
+        &mint_account.owner,
+        args.name,
+        args.symbol,
+        args.uri,
+    );
+    // Token-2022 mints keep the metadata in the mint, without the metadata account
+    let watched = if mint_account.owner == anchor_spl::token::ID {
+        vec![Watched::new("metadata", pda::metadata_address(&token_mint).0, AccountKind::TokenMetadata)]
+    } else {
+        vec![]
+    };
+    session.execute(&payer, &[instruction], &[], &watched)
+}
+
+pub fn show_config(session: &Session, args: SaleArgs) -> Result<()> {
+    let (config_address, _) = pda::config_address(&args.mint);
+    let config: NsdConfig = session.fetch(&config_address, "NSD config")?;
+    display::print_fields("config", &config_address, &display::config_fields(&config));
+
+    let (treasury, _) = pda::treasury_address(&config_address);
+    let lamports = session.snapshot(&treasury)?.map_or(0, |account| account.lamports);
+    println!("treasury {treasury}");
+    println!("  lamports  {lamports}");
+    Ok(())
+}
+
+pub fn show_user(session: &Session, args: ShowUserArgs) -> Result<()> {
+    let (config, _) = pda::config_address(&args.sale.mint);
+    let (user_address, _) = pda::user_address(&config, &args.user);
+    let user: NsdUser = session.fetch(&user_address, "NSD user")?;
+    display::print_fields("user", &user_address, &display::user_fields(&user));
+    Ok(())
+}
+
+pub fn withdraw(session: &Session, args: WithdrawArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+    let destination = args.destination.unwrap_or_else(|| payer.pubkey());
+    let (config, _) = pda::config_address(&token_mint);
+
+    let instruction = instructions::withdraw_treasury(&payer.pubkey(), &payer.pubkey(), &token_mint, &destination, args.amount);
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[
+            Watched::new("config", config, AccountKind::Config),
+            Watched::new("treasury", pda::treasury_address(&config).0, AccountKind::Lamports),
+            Watched::new("destination", destination, AccountKind::Lamports),
+        ],
+    )
+}
+
+pub fn withdraw_tokens(session: &Session, args: WithdrawTokensArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+    let (config, _) = pda::config_address(&token_mint);
+    let Some(payment_mint) = session.snapshot(&args.payment_mint)? else {
+        bail!("payment mint {} does not exist", args.payment_mint);
+    };
+    let payment_token_program = payment_mint.owner;
+    let destination = args
+        .destination
+        .unwrap_or_else(|| get_associated_token_address_with_program_id(&payer.pubkey(), &args.payment_mint, &payment_token_program));
+
+    let instruction = instructions::withdraw_payment_tokens(
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &token_mint,
+        &args.payment_mint,
+        &payment_token_program,
+        &destination,
+        args.amount,
+    );
+    session.execute(
+        &payer,
+        &[instruction],
+        &[],
+        &[
+            Watched::new(
+                "treasury token account",
+                pda::treasury_payment_account(&config, &args.payment_mint, &payment_token_program),
+                AccountKind::TokenAccount,
+            ),
+            Watched::new("destination", destination, AccountKind::TokenAccount),
+        ],
+    )
+}
+
+pub fn finalize(session: &Session, args: FinalizeArgs) -> Result<()> {
+    let payer = session.payer()?;
+    let token_mint = args.sale.mint;
+    let (config, _) = pda::config_address(&token_mint);
+    let Some(mint_account) = session.snapshot(&token_mint)? else {
+        bail!("token mint {token_mint} does not exist");
+    };
+
+    let instruction =
+        instructions::finalize_sale(&payer.pubkey(), &payer.pubkey(), &token_mint, &mint_account.owner, args.sweep_to.as_ref());
+    let mut watched = vec![
+        Watched::new("config", config, AccountKind::Config),
+        Watched::new("treasury", pda::treasury_address(&config).0, AccountKind::Lamports),
+    ];
+    if let Some(destination) = args.sweep_to {
+        watched.push(Watched::new("destination", destination, AccountKind::Lamports));
+    }
+    session.execute(&payer, &[instruction], &[], &watched)
+}
@@ -0,0 +1,168 @@
+//! NSD Admin - command-line tool for the NSD Minting Program
+//!
+//! Rendering of the program accounts as named fields, for the `show-*` commands
+//! and for the before/after diffs printed by `--simulate`.
+
+// Import required modules and macros
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token_interface::TokenAccount;
+use nsd_minting_client::{accounts, NsdConfig, NsdTokenMetadata, NsdUser, PaymentMint, PriceTier};
+
+/// Raw state of an account at one point in time
+#[derive(Clone)]
+pub struct Snapshot {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+}
+
+/// How to decode a watched account
+#[derive(Clone, Copy)]
+pub enum AccountKind {
+    Config,
+    TokenMetadata,
+    /// Only the lamport balance matters, e.g. the treasury
+    Lamports,
+    /// SPL Token or Token-2022 account, shown by its balance
+    TokenAccount,
+}
+
+/// Account whose changes are printed when simulating
+pub struct Watched {
+    pub label: &'static str,
+    pub address: Pubkey,
+    pub kind: AccountKind,
+}
+
+impl Watched {
+    pub fn new(label: &'static str, address: Pubkey, kind: AccountKind) -> Self {
+        Self { label, address, kind }
+    }
+}
+
+type Fields = Vec<(&'static str, String)>;
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map_or_else(|| "none".to_string(), T::to_string)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn tiers(tiers: &[PriceTier]) -> String {
+    if tiers.is_empty() {
+        return "none".to_string();
+    }
+    tiers.iter().map(|tier| format!("{}:{}", tier.start_supply, tier.price)).collect::<Vec<_>>().join(" ")
+}
+
+fn payment_mints(payment_mints: &[PaymentMint]) -> String {
+    if payment_mints.is_empty() {
+        return "none".to_string();
+    }
+    payment_mints.iter().map(|payment| format!("{}:{}", payment.mint, payment.price)).collect::<Vec<_>>().join(" ")
+}
+
+pub fn config_fields(config: &NsdConfig) -> Fields {
+    vec![
+        ("admin", config.admin.to_string()),
+        ("pending_admin", optional(&config.pending_admin)),
+        ("token_mint", config.token_mint.to_string()),
+        ("decimals", config.decimals.to_string()),
+        ("is_active", config.is_active.to_string()),
+        ("is_finalized", config.is_finalized.to_string()),
+        ("max_supply", config.max_supply.to_string()),
+        ("max_supply_frozen", config.max_supply_frozen.to_string()),
+        ("total_minted", config.total_minted.to_string()),
+        ("mint_price", config.mint_price.to_string()),
+        ("min_price", optional(&config.min_price)),
+        ("max_price", optional(&config.max_price)),
+        ("total_revenue", config.total_revenue.to_string()),
+        ("total_withdrawn", config.total_withdrawn.to_string()),
+        ("mint_authority_bound", config.mint_authority_bound.to_string()),
+        ("max_per_wallet", optional(&config.max_per_wallet)),
+        ("max_per_transaction", config.max_per_transaction.to_string()),
+        ("cooldown_seconds", config.cooldown_seconds.to_string()),
+        ("allowlist_root", optional(&config.allowlist_root.map(|root| hex(&root)))),
+        ("phase_count", config.phase_count.to_string()),
+        ("price_tiers", tiers(&config.price_tiers)),
+        ("bonding_curve", optional(&config.bonding_curve.map(|curve| format!("{} + {}n", curve.base_price, curve.slope)))),
+        ("total_refunded", config.total_refunded.to_string()),
+        (
+            "dutch_auction",
+            optional(&config.dutch_auction.map(|auction| {
+                format!(
+                    "{} -> {} from {} over {}s",
+                    auction.start_price, auction.floor_price, auction.start_timestamp, auction.decay_interval
+                )
+            })),
+        ),
+        ("payment_mints", payment_mints(&config.payment_mints)),
+    ]
+}
+
+pub fn user_fields(user: &NsdUser) -> Fields {
+    vec![
+        ("user", user.user.to_string()),
+        ("tokens_minted", user.tokens_minted.to_string()),
+        ("last_mint_timestamp", user.last_mint_timestamp.to_string()),
+    ]
+}
+
+pub fn token_metadata_fields(metadata: &NsdTokenMetadata) -> Fields {
+    vec![
+        ("mint", metadata.mint.to_string()),
+        ("name", metadata.name.clone()),
+        ("symbol", metadata.symbol.clone()),
+        ("uri", metadata.uri.clone()),
+        ("is_locked", metadata.is_locked.to_string()),
+    ]
+}
+
+fn fields(kind: AccountKind, snapshot: &Snapshot) -> Result<Fields> {
+    Ok(match kind {
+        AccountKind::Config => config_fields(&accounts::deserialize_config(&snapshot.data)?),
+        AccountKind::TokenMetadata => token_metadata_fields(&accounts::deserialize_token_metadata(&snapshot.data)?),
+        AccountKind::Lamports => vec![("lamports", snapshot.lamports.to_string())],
+        AccountKind::TokenAccount => {
+            let account = TokenAccount::try_deserialize(&mut snapshot.data.as_slice())?;
+            vec![("mint", account.mint.to_string()), ("owner", account.owner.to_string()), ("amount", account.amount.to_string())]
+        }
+    })
+}
+
+/// Print every field of an account under a title
+pub fn print_fields(title: &str, address: &Pubkey, fields: &Fields) {
+    println!("{title} {address}");
+    let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+    for (name, value) in fields {
+        println!("  {name:<width$}  {value}");
+    }
+}
+
+/// Print how an account changes, field by field
+pub fn print_diff(account: &Watched, before: Option<&Snapshot>, after: Option<&Snapshot>) -> Result<()> {
+    let before = before.map(|snapshot| fields(account.kind, snapshot)).transpose()?;
+    let after = after.map(|snapshot| fields(account.kind, snapshot)).transpose()?;
+
+    match (before, after) {
+        (None, None) => {}
+        (None, Some(after)) => print_fields(&format!("{} (created)", account.label), &account.address, &after),
+        (Some(_), None) => println!("{} {} (closed)", account.label, account.address),
+        (Some(before), Some(after)) => {
+            let changes: Vec<_> = before.iter().zip(&after).filter(|(old, new)| old.1 != new.1).collect();
+            if changes.is_empty() {
+                println!("{} {} (unchanged)", account.label, account.address);
+            } else {
+                println!("{} {}", account.label, account.address);
+                for ((name, old), (_, new)) in changes {
+                    println!("  {name}: {old} -> {new}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,299 @@
+//! NSD Admin - command-line tool for the NSD Minting Program
+//!
+//! Operates a sale from the terminal: create it, change its settings and metadata,
+//! inspect its accounts, withdraw the treasury and finalize it. Keypairs are read from files and
+//! any RPC URL works, including a local test validator.
+//!
+//! With `--simulate` no transaction is sent; the transaction is simulated and the
+//! decoded accounts it would change are printed as a diff.
+
+// Import required modules and macros
+mod commands;
+mod display;
+mod session;
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use clap::{Args, Parser, Subcommand};
+use nsd_minting_client::{PaymentMint, PriceTier};
+
+use crate::session::Session;
+
+#[derive(Parser)]
+#[command(name = "nsd-admin", version, about = "Operate NSD minting sales")]
+struct Cli {
+    /// RPC URL or moniker (localhost, devnet, testnet, mainnet-beta)
+    #[arg(short, long, global = true, default_value = "localhost")]
+    url: String,
+
+    /// Admin keypair file, also paying the fees
+    #[arg(short, long, global = true)]
+    keypair: Option<PathBuf>,
+
+    /// Simulate the transaction and print the account diffs instead of sending it
+    #[arg(long, global = true)]
+    simulate: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new token mint and its sale, with the admin keypair as admin
+    Init(InitArgs),
+    /// Change the supply cap, price or active flag of a sale
+    UpdateConfig(UpdateConfigArgs),
+    /// Permanently stop any increase of the maximum supply
+    FreezeMaxSupply(SaleArgs),
+    /// Replace the price tiers, or restore the flat price when none are given
+    SetPriceTiers(SetPriceTiersArgs),
+    /// Set or clear the bonding curve pricing mints and sells
+    SetBondingCurve(SetBondingCurveArgs),
+    /// Set or clear the Dutch auction decaying the price over time
+    SetDutchAuction(SetDutchAuctionArgs),
+    /// Replace the SPL tokens accepted as payment, or accept only lamports when none are given
+    SetPaymentMints(SetPaymentMintsArgs),
+    /// Set the token name, symbol and URI
+    SetMetadata(SetMetadataArgs),
+    /// Print the configuration and treasury balance of a sale
+    ShowConfig(SaleArgs),
+    /// Print the minting history of a wallet
+    ShowUser(ShowUserArgs),
+    /// Withdraw lamports from the treasury
+    Withdraw(WithdrawArgs),
+    /// Withdraw SPL token payments from the treasury token account
+    WithdrawTokens(WithdrawTokensArgs),
+    /// End the sale for good by revoking the mint authority
+    Finalize(FinalizeArgs),
+}
+
+/// Sale selected by its token mint
+#[derive(Args)]
+pub struct SaleArgs {
+    /// Token mint of the sale
+    #[arg(short, long)]
+    pub mint: Pubkey,
+}
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// Keypair file of the new token mint, generated when omitted
+    #[arg(long)]
+    pub mint_keypair: Option<PathBuf>,
+
+    /// Create the mint under Token-2022 instead of SPL Token
+    #[arg(long)]
+    pub token_2022: bool,
+
+    /// Decimals of the new mint
+    #[arg(long, default_value_t = 0)]
+    pub decimals: u8,
+
+    /// Maximum number of tokens that can be minted
+    #[arg(long)]
+    pub max_supply: u64,
+
+    /// Price per token in lamports
+    #[arg(long)]
+    pub price: u64,
+
+    /// Lowest price per token the admin may ever set
+    #[arg(long)]
+    pub min_price: Option<u64>,
+
+    /// Highest price per token the admin may ever set
+    #[arg(long)]
+    pub max_price: Option<u64>,
+}
+
+#[derive(Args)]
+pub struct UpdateConfigArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// New maximum supply
+    #[arg(long)]
+    pub max_supply: Option<u64>,
+
+    /// New price per token in lamports
+    #[arg(long)]
+    pub price: Option<u64>,
+
+    /// Open or pause minting
+    #[arg(long)]
+    pub active: Option<bool>,
+}
+
+#[derive(Args)]
+pub struct SetPriceTiersArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Tier as START_SUPPLY:PRICE, repeated in ascending order starting at 0
+    #[arg(long = "tier", value_parser = parse_tier)]
+    pub tiers: Vec<PriceTier>,
+}
+
+/// Parse a `START_SUPPLY:PRICE` tier
+fn parse_tier(value: &str) -> Result<PriceTier, String> {
+    let (start_supply, price) = value.split_once(':').ok_or("expected START_SUPPLY:PRICE")?;
+    Ok(PriceTier {
+        start_supply: start_supply.parse().map_err(|err| format!("invalid start supply: {err}"))?,
+        price: price.parse().map_err(|err| format!("invalid price: {err}"))?,
+    })
+}
+
+#[derive(Args)]
+pub struct SetBondingCurveArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Price in lamports of the first token
+    #[arg(long, required_unless_present = "clear")]
+    pub base_price: Option<u64>,
+
+    /// Price increase in lamports per token sold
+    #[arg(long, required_unless_present = "clear")]
+    pub slope: Option<u64>,
+
+    /// Remove the curve and return to the mint price
+    #[arg(long, conflicts_with_all = ["base_price", "slope"])]
+    pub clear: bool,
+}
+
+#[derive(Args)]
+pub struct SetDutchAuctionArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Price per token in lamports when the auction starts
+    #[arg(long, required_unless_present = "clear")]
+    pub start_price: Option<u64>,
+
+    /// Lowest price per token in lamports
+    #[arg(long, required_unless_present = "clear")]
+    pub floor_price: Option<u64>,
+
+    /// Unix timestamp at which the price starts to decay
+    #[arg(long, required_unless_present = "clear")]
+    pub start: Option<i64>,
+
+    /// Seconds over which the price falls to the floor
+    #[arg(long, required_unless_present = "clear")]
+    pub decay_interval: Option<u64>,
+
+    /// Remove the auction and return to the mint price
+    #[arg(long, conflicts_with_all = ["start_price", "floor_price", "start", "decay_interval"])]
+    pub clear: bool,
+}
+
+#[derive(Args)]
+pub struct SetPaymentMintsArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Accepted token as MINT:PRICE, the price in base units of that token per NSD token
+    #[arg(long = "payment-mint", value_parser = parse_payment_mint)]
+    pub payment_mints: Vec<PaymentMint>,
+}
+
+/// Parse a `MINT:PRICE` payment mint
+fn parse_payment_mint(value: &str) -> Result<PaymentMint, String> {
+    let (mint, price) = value.split_once(':').ok_or("expected MINT:PRICE")?;
+    Ok(PaymentMint {
+        mint: mint.parse().map_err(|err| format!("invalid mint: {err}"))?,
+        price: price.parse().map_err(|err| format!("invalid price: {err}"))?,
+    })
+}
+
+#[derive(Args)]
+pub struct SetMetadataArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Token name
+    #[arg(long)]
+    pub name: String,
+
+    /// Token symbol
+    #[arg(long)]
+    pub symbol: String,
+
+    /// Token metadata URI
+    #[arg(long)]
+    pub uri: String,
+}
+
+#[derive(Args)]
+pub struct ShowUserArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Wallet to show the minting history of
+    pub user: Pubkey,
+}
+
+#[derive(Args)]
+pub struct WithdrawArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Lamports to withdraw
+    #[arg(long)]
+    pub amount: u64,
+
+    /// Account receiving the lamports, the admin by default
+    #[arg(long)]
+    pub destination: Option<Pubkey>,
+}
+
+#[derive(Args)]
+pub struct WithdrawTokensArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Mint of the payment token to withdraw
+    #[arg(long)]
+    pub payment_mint: Pubkey,
+
+    /// Amount to withdraw, in base units of the payment token
+    #[arg(long)]
+    pub amount: u64,
+
+    /// Token account receiving the tokens, the admin's associated token account by default
+    #[arg(long)]
+    pub destination: Option<Pubkey>,
+}
+
+#[derive(Args)]
+pub struct FinalizeArgs {
+    #[command(flatten)]
+    pub sale: SaleArgs,
+
+    /// Sweep the remaining treasury balance to this account
+    #[arg(long)]
+    pub sweep_to: Option<Pubkey>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let session = Session::new(&cli.url, cli.keypair.as_deref(), cli.simulate)?;
+
+    match cli.command {
+        Command::Init(args) => commands::init(&session, args),
+        Command::UpdateConfig(args) => commands::update_config(&session, args),
+        Command::FreezeMaxSupply(args) => commands::freeze_max_supply(&session, args),
+        Command::SetPriceTiers(args) => commands::set_price_tiers(&session, args),
+        Command::SetBondingCurve(args) => commands::set_bonding_curve(&session, args),
+        Command::SetDutchAuction(args) => commands::set_dutch_auction(&session, args),
+        Command::SetPaymentMints(args) => commands::set_payment_mints(&session, args),
+        Command::SetMetadata(args) => commands::set_metadata(&session, args),
+        Command::ShowConfig(args) => commands::show_config(&session, args),
+        Command::ShowUser(args) => commands::show_user(&session, args),
+        Command::Withdraw(args) => commands::withdraw(&session, args),
+        Command::WithdrawTokens(args) => commands::withdraw_tokens(&session, args),
+        Command::Finalize(args) => commands::finalize(&session, args),
+    }
+}
@@ -0,0 +1,142 @@
+//! NSD Admin - command-line tool for the NSD Minting Program
+//!
+//! RPC session shared by the commands: reads accounts, and either sends a transaction
+//! or simulates it and prints the decoded diffs of the accounts it touches.
+
+// Import required modules and macros
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, bail, Context, Result};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::display::{self, Snapshot, Watched};
+
+pub struct Session {
+    client: RpcClient,
+    keypair: PathBuf,
+    simulate: bool,
+}
+
+impl Session {
+    /// Connect to `url`, a full RPC URL or one of the Solana CLI monikers
+    pub fn new(url: &str, keypair: Option<&Path>, simulate: bool) -> Result<Self> {
+        let keypair = match keypair {
+            Some(path) => path.to_path_buf(),
+            None => default_keypair_path()?,
+        };
+        Ok(Self {
+            client: RpcClient::new_with_commitment(resolve_url(url), CommitmentConfig::confirmed()),
+            keypair,
+            simulate,
+        })
+    }
+
+    /// Admin keypair, which also pays the fees
+    pub fn payer(&self) -> Result<Keypair> {
+        read_keypair(&self.keypair)
+    }
+
+    /// Current state of an account, `None` if it does not exist
+    pub fn snapshot(&self, address: &Pubkey) -> Result<Option<Snapshot>> {
+        let account = self
+            .client
+            .get_account_with_commitment(address, self.client.commitment())
+            .with_context(|| format!("failed to fetch account {address}"))?
+            .value;
+        Ok(account.map(|account| Snapshot { lamports: account.lamports, data: account.data, owner: account.owner }))
+    }
+
+    /// Fetch and decode a program account
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey, name: &str) -> Result<T> {
+        let snapshot = self.snapshot(address)?.ok_or_else(|| anyhow!("{name} account {address} does not exist"))?;
+        T::try_deserialize(&mut &snapshot.data[..]).with_context(|| format!("{address} is not an {name} account"))
+    }
+
+    /// Send the instructions in one transaction, or simulate it with `--simulate`
+    ///
+    /// The payer signs first; `signers` adds any other required signatures. When simulating,
+    /// the accounts in `watched` are fetched before and after and their decoded diffs printed.
+    pub fn execute(&self, payer: &Keypair, instructions: &[Instruction], signers: &[&Keypair], watched: &[Watched]) -> Result<()> {
+        let mut all_signers: Vec<&dyn Signer> = vec![payer];
+        all_signers.extend(signers.iter().map(|signer| *signer as &dyn Signer));
+        let blockhash = self.client.get_latest_blockhash().context("failed to fetch a recent blockhash")?;
+        let transaction = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all_signers, blockhash);
+
+        if !self.simulate {
+            let signature = self.client.send_and_confirm_transaction(&transaction).context("transaction failed")?;
+            println!("Signature: {signature}");
+            return Ok(());
+        }
+
+        let before = watched.iter().map(|account| self.snapshot(&account.address)).collect::<Result<Vec<_>>>()?;
+        let result = self
+            .client
+            .simulate_transaction_with_config(
+                &transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: true,
+                    commitment: Some(self.client.commitment()),
+                    accounts: Some(RpcSimulateTransactionAccountsConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        addresses: watched.iter().map(|account| account.address.to_string()).collect(),
+                    }),
+                    ..Default::default()
+                },
+            )
+            .context("simulation request failed")?
+            .value;
+
+        if let Some(err) = result.err {
+            for log in result.logs.unwrap_or_default() {
+                eprintln!("  {log}");
+            }
+            bail!("simulation failed: {err:?}");
+        }
+
+        println!("Simulation succeeded, {} compute units", result.units_consumed.unwrap_or_default());
+        let after = result.accounts.unwrap_or_default();
+        for (index, account) in watched.iter().enumerate() {
+            let after = match after.get(index).cloned().flatten() {
+                Some(ui_account) => Some(Snapshot {
+                    lamports: ui_account.lamports,
+                    data: ui_account.data.decode().ok_or_else(|| anyhow!("undecodable simulated account {}", account.address))?,
+                    owner: ui_account.owner.parse()?,
+                }),
+                None => None,
+            };
+            display::print_diff(account, before[index].as_ref(), after.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+/// Translate the Solana CLI cluster monikers into RPC URLs
+fn resolve_url(url: &str) -> String {
+    match url {
+        "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+    .to_string()
+}
+
+/// The Solana CLI default keypair, `~/.config/solana/id.json`
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set, pass --keypair"))?;
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
+
+/// Read a keypair file written by `solana-keygen`
+pub fn read_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {err}", path.display()))
+}
@@ -0,0 +1,74 @@
+//! NSD Admin - command-line tool for the NSD Minting Program
+//!
+//! Argument handling tests. These run the built binary and never reach an RPC node.
+
+// Import required modules and macros
+use std::process::{Command, Output};
+
+fn nsd_admin(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_nsd-admin")).args(args).output().unwrap()
+}
+
+#[test]
+fn help_lists_every_subcommand() {
+    let output = nsd_admin(&["--help"]);
+    assert!(output.status.success());
+    let help = String::from_utf8(output.stdout).unwrap();
+    for command in ["init", "update-config", "freeze-max-supply", "set-price-tiers", "set-bonding-curve", "set-dutch-auction", "set-payment-mints", "set-metadata", "show-config", "show-user", "withdraw", "withdraw-tokens", "finalize", "--simulate"] {
+        assert!(help.contains(command), "missing {command} in:\n{help}");
+    }
+}
+
+#[test]
+fn update_config_requires_a_change() {
+    let mint = "So11111111111111111111111111111111111111112";
+    let output = nsd_admin(&["update-config", "--mint", mint, "--simulate"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("nothing to update"));
+}
+
+#[test]
+fn invalid_pubkeys_are_rejected() {
+    let output = nsd_admin(&["show-config", "--mint", "not-a-pubkey"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("--mint"));
+}
+
+#[test]
+fn malformed_price_tiers_are_rejected() {
+    let mint = "So11111111111111111111111111111111111111112";
+    let output = nsd_admin(&["set-price-tiers", "--mint", mint, "--tier", "0:100", "--tier", "1000"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("expected START_SUPPLY:PRICE"));
+}
+
+#[test]
+fn malformed_payment_mints_are_rejected() {
+    let mint = "So11111111111111111111111111111111111111112";
+    let output = nsd_admin(&["set-payment-mints", "--mint", mint, "--payment-mint", mint]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("expected MINT:PRICE"));
+
+    let output = nsd_admin(&["set-payment-mints", "--mint", mint, "--payment-mint", "not-a-pubkey:100"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("invalid mint"));
+}
+
+#[test]
+fn bonding_curve_needs_both_parameters_or_clear() {
+    let mint = "So11111111111111111111111111111111111111112";
+    let output = nsd_admin(&["set-bonding-curve", "--mint", mint, "--base-price", "100"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("--slope"));
+
+    let output = nsd_admin(&["set-bonding-curve", "--mint", mint, "--clear", "--slope", "1"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn missing_keypair_is_reported() {
+    let mint = "So11111111111111111111111111111111111111112";
+    let output = nsd_admin(&["withdraw", "--mint", mint, "--amount", "1", "--keypair", "/nonexistent/id.json"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("failed to read keypair /nonexistent/id.json"));
+}
@@ -0,0 +1,33 @@
+//! NSD Minting Client - Rust SDK for the NSD Minting Program
+//!
+//! Account deserializers. Each checks the Anchor discriminator before decoding,
+//! so passing the data of another account type fails instead of returning garbage.
+
+// Import required modules and macros
+use anchor_lang::{AccountDeserialize, Result};
+use nsd_minting::{NsdConfig, NsdTokenMetadata, NsdUser, SalePhase};
+
+/// Decode any NSD minting program account from its raw data
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Decode a configuration account
+pub fn deserialize_config(data: &[u8]) -> Result<NsdConfig> {
+    deserialize(data)
+}
+
+/// Decode a user minting history account
+pub fn deserialize_user(data: &[u8]) -> Result<NsdUser> {
+    deserialize(data)
+}
+
+/// Decode a token metadata account
+pub fn deserialize_token_metadata(data: &[u8]) -> Result<NsdTokenMetadata> {
+    deserialize(data)
+}
+
+/// Decode a sale phase account
+pub fn deserialize_sale_phase(data: &[u8]) -> Result<SalePhase> {
+    deserialize(data)
+}
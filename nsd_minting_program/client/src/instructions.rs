@@ -0,0 +1,625 @@
+//! NSD Minting Client - Rust SDK for the NSD Minting Program
+//!
+//! Instruction builders, one per program instruction. Each builder derives the PDAs of
+//! the sale from its token mint and fills in the program accounts, so callers only pass
+//! the signers, the mint and the instruction arguments.
+
+// Import required modules and macros
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, Result, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::metadata::mpl_token_metadata;
+use nsd_minting::{accounts, instruction, BondingCurve, DutchAuction, NsdConfig, PaymentMint, PriceTier, SalePhase, UpdateConfigArgs};
+
+use crate::{pda, quote};
+
+/// Build an instruction for the NSD minting program
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Metadata and Metaplex accounts for SPL Token mints, omitted for Token-2022 mints
+fn metadata_accounts(token_mint: &Pubkey, token_program: &Pubkey) -> (Option<Pubkey>, Option<Pubkey>, Option<Pubkey>) {
+    if *token_program == anchor_spl::token::ID {
+        (
+            Some(pda::metadata_address(token_mint).0),
+            Some(pda::metaplex_metadata_address(token_mint).0),
+            Some(mpl_token_metadata::ID),
+        )
+    } else {
+        (None, None, None)
+    }
+}
+
+/// Accounts shared by the mint instructions
+#[derive(Clone, Debug)]
+pub struct MintAccounts {
+    /// Pays the mint price and the rent of new accounts
+    pub fee_payer: Pubkey,
+    /// Wallet receiving the tokens
+    pub user: Pubkey,
+    /// Token mint of the sale
+    pub token_mint: Pubkey,
+    /// Token program owning the mint, SPL Token or Token-2022
+    pub token_program: Pubkey,
+    /// Sale phase to mint in, required once the sale has phases
+    pub sale_phase: Option<u8>,
+}
+
+impl MintAccounts {
+    fn config(&self) -> Pubkey {
+        pda::config_address(&self.token_mint).0
+    }
+
+    fn user_token_account(&self) -> Pubkey {
+        get_associated_token_address_with_program_id(&self.user, &self.token_mint, &self.token_program)
+    }
+
+    fn sale_phase(&self) -> Option<Pubkey> {
+        let config = self.config();
+        self.sale_phase.map(|phase_id| pda::sale_phase_address(&config, phase_id).0)
+    }
+
+    fn mint_tokens_v2(&self) -> accounts::MintTokensV2 {
+        let config = self.config();
+        accounts::MintTokensV2 {
+            fee_payer: self.fee_payer,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            token_mint: self.token_mint,
+            user: self.user,
+            user_account: pda::user_address(&config, &self.user).0,
+            user_token_account: self.user_token_account(),
+            mint_authority: pda::mint_authority_address(&config).0,
+            token_program: self.token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            sale_phase: self.sale_phase(),
+        }
+    }
+}
+
+/// `initialize_config` for an externally created mint
+pub fn initialize_config(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    max_supply: u64,
+    mint_price: u64,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    build(
+        accounts::InitializeConfig {
+            fee_payer: *fee_payer,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { token_mint: *token_mint, max_supply, mint_price, min_price, max_price },
+    )
+}
+
+/// `initialize_sale`, creating `token_mint` under `token_program`
+///
+/// The token mint keypair must sign the transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_sale(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    decimals: u8,
+    max_supply: u64,
+    mint_price: u64,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    build(
+        accounts::InitializeSale {
+            fee_payer: *fee_payer,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            admin: *admin,
+            token_mint: *token_mint,
+            mint_authority: pda::mint_authority_address(&config).0,
+            freeze_authority: pda::freeze_authority_address(&config).0,
+            token_program: *token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeSale { decimals, max_supply, mint_price, min_price, max_price },
+    )
+}
+
+/// `mint_tokens`, the legacy mint layout
+pub fn mint_tokens(mint: &MintAccounts, mint_amount: u64, max_total_cost: u64) -> Instruction {
+    let config = mint.config();
+    let user_token_account = mint.user_token_account();
+    build(
+        accounts::MintTokens {
+            fee_payer: mint.fee_payer,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            user_token_account,
+            user: mint.user,
+            user_account: pda::user_address(&config, &mint.user).0,
+            token_mint: mint.token_mint,
+            system_program: system_program::ID,
+            mint: mint.token_mint,
+            assoc_token_account: user_token_account,
+            mint_authority: pda::mint_authority_address(&config).0,
+            wallet: mint.user,
+            token_program: mint.token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            sale_phase: mint.sale_phase(),
+        },
+        instruction::MintTokens { mint_amount, max_total_cost },
+    )
+}
+
+/// `mint_tokens` capped at the quote for a freshly fetched configuration, see [`quoted_mint_tokens_v2`]
+pub fn quoted_mint_tokens(mint: &MintAccounts, config: &NsdConfig, phase: Option<&SalePhase>, mint_amount: u64, now: i64) -> Result<Instruction> {
+    let max_total_cost = quote::mint_cost(config, phase, mint_amount, now)?;
+    Ok(mint_tokens(mint, mint_amount, max_total_cost))
+}
+
+/// `mint_tokens_v2`
+pub fn mint_tokens_v2(mint: &MintAccounts, mint_amount: u64, max_total_cost: u64) -> Instruction {
+    build(mint.mint_tokens_v2(), instruction::MintTokensV2 { mint_amount, max_total_cost })
+}
+
+/// `mint_tokens_v2` capped at the quote for a freshly fetched configuration
+///
+/// `phase` is the account of `mint.sale_phase`, if any. The mint fails with `PriceExceedsLimit`
+/// instead of charging more than quoted if the price rises before it lands.
+pub fn quoted_mint_tokens_v2(mint: &MintAccounts, config: &NsdConfig, phase: Option<&SalePhase>, mint_amount: u64, now: i64) -> Result<Instruction> {
+    let max_total_cost = quote::mint_cost(config, phase, mint_amount, now)?;
+    Ok(mint_tokens_v2(mint, mint_amount, max_total_cost))
+}
+
+/// `presale_mint` with the allowlist proof for `allocation`
+pub fn presale_mint(mint: &MintAccounts, mint_amount: u64, max_total_cost: u64, allocation: u64, proof: Vec<[u8; 32]>) -> Instruction {
+    build(mint.mint_tokens_v2(), instruction::PresaleMint { mint_amount, max_total_cost, allocation, proof })
+}
+
+/// `update_config`
+pub fn update_config(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, args: UpdateConfigArgs) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            admin: *admin,
+        },
+        instruction::UpdateConfig { args },
+    )
+}
+
+/// `withdraw_treasury` into `destination`
+pub fn withdraw_treasury(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    build(
+        accounts::WithdrawTreasury {
+            fee_payer: *fee_payer,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            admin: *admin,
+            destination: *destination,
+        },
+        instruction::WithdrawTreasury { amount },
+    )
+}
+
+/// `bind_mint_authority`, after the mint authority was handed to the PDA
+pub fn bind_mint_authority(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    build(
+        accounts::BindMintAuthority {
+            fee_payer: *fee_payer,
+            config,
+            admin: *admin,
+            token_mint: *token_mint,
+            mint_authority: pda::mint_authority_address(&config).0,
+        },
+        instruction::BindMintAuthority {},
+    )
+}
+
+/// `set_token_metadata`
+///
+/// The metadata and Metaplex accounts are passed for SPL Token mints; Token-2022 mints keep the metadata in the mint.
+pub fn set_token_metadata(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    let (metadata, metaplex_metadata, token_metadata_program) = metadata_accounts(token_mint, token_program);
+    build(
+        accounts::SetTokenMetadata {
+            fee_payer: *fee_payer,
+            config,
+            metadata,
+            admin: *admin,
+            mint: *token_mint,
+            mint_authority: pda::mint_authority_address(&config).0,
+            metaplex_metadata,
+            token_metadata_program,
+            token_program: *token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::SetTokenMetadata { token_mint: *token_mint, name, symbol, uri },
+    )
+}
+
+/// `update_token_metadata`, leaving fields passed as `None` unchanged
+pub fn update_token_metadata(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    let (metadata, metaplex_metadata, token_metadata_program) = metadata_accounts(token_mint, token_program);
+    build(
+        accounts::UpdateTokenMetadata {
+            fee_payer: *fee_payer,
+            config,
+            metadata,
+            admin: *admin,
+            mint_authority: pda::mint_authority_address(&config).0,
+            mint: *token_mint,
+            metaplex_metadata,
+            token_metadata_program,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateTokenMetadata { name, symbol, uri },
+    )
+}
+
+/// `lock_metadata`
+pub fn lock_metadata(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, token_program: &Pubkey) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    let (metadata, metaplex_metadata, token_metadata_program) = metadata_accounts(token_mint, token_program);
+    build(
+        accounts::LockMetadata {
+            fee_payer: *fee_payer,
+            config,
+            metadata,
+            admin: *admin,
+            mint_authority: pda::mint_authority_address(&config).0,
+            mint: *token_mint,
+            metaplex_metadata,
+            token_metadata_program,
+            token_program: *token_program,
+        },
+        instruction::LockMetadata {},
+    )
+}
+
+/// Append the sale phases `phase_ids` of `config` as read-only remaining accounts
+fn with_phases(mut instruction: Instruction, config: &Pubkey, phase_ids: &[u8]) -> Instruction {
+    instruction.accounts.extend(
+        phase_ids.iter().map(|phase_id| AccountMeta::new_readonly(pda::sale_phase_address(config, *phase_id).0, false)),
+    );
+    instruction
+}
+
+/// `create_sale_phase` with the phase id, window, price and limits in `args`
+///
+/// `existing_phase_ids` lists every phase the sale already has, checked for overlapping windows.
+pub fn create_sale_phase(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    args: instruction::CreateSalePhase,
+    existing_phase_ids: &[u8],
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    let instruction = build(
+        accounts::CreateSalePhase {
+            fee_payer: *fee_payer,
+            config,
+            sale_phase: pda::sale_phase_address(&config, args.phase_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        args,
+    );
+    with_phases(instruction, &config, existing_phase_ids)
+}
+
+/// `update_sale_phase` for phase `phase_id`
+///
+/// `other_phase_ids` lists every other phase of the sale, checked for overlapping windows
+/// when `args` moves the window; it may be empty otherwise.
+pub fn update_sale_phase(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    phase_id: u8,
+    args: instruction::UpdateSalePhase,
+    other_phase_ids: &[u8],
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    let instruction = build(
+        accounts::UpdateSalePhase {
+            fee_payer: *fee_payer,
+            config,
+            sale_phase: pda::sale_phase_address(&config, phase_id).0,
+            admin: *admin,
+        },
+        args,
+    );
+    with_phases(instruction, &config, other_phase_ids)
+}
+
+/// `close_sale_phase` for phase `phase_id`, refunding its rent to the fee payer
+pub fn close_sale_phase(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, phase_id: u8) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    build(
+        accounts::CloseSalePhase {
+            fee_payer: *fee_payer,
+            config,
+            sale_phase: pda::sale_phase_address(&config, phase_id).0,
+            admin: *admin,
+        },
+        instruction::CloseSalePhase {},
+    )
+}
+
+/// `propose_admin`
+pub fn propose_admin(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::ProposeAdmin {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            admin: *admin,
+        },
+        instruction::ProposeAdmin { new_admin: *new_admin },
+    )
+}
+
+/// `accept_admin`, signed by the proposed admin
+pub fn accept_admin(fee_payer: &Pubkey, new_admin: &Pubkey, token_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdmin {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            new_admin: *new_admin,
+        },
+        instruction::AcceptAdmin {},
+    )
+}
+
+/// `cancel_admin_transfer`
+pub fn cancel_admin_transfer(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelAdminTransfer {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            admin: *admin,
+        },
+        instruction::CancelAdminTransfer {},
+    )
+}
+
+/// `freeze_max_supply`
+pub fn freeze_max_supply(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::FreezeMaxSupply {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            admin: *admin,
+        },
+        instruction::FreezeMaxSupply {},
+    )
+}
+
+/// `set_price_tiers`, an empty list restoring the flat mint price
+pub fn set_price_tiers(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, tiers: Vec<PriceTier>) -> Instruction {
+    build(
+        accounts::SetPriceTiers {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            admin: *admin,
+        },
+        instruction::SetPriceTiers { tiers },
+    )
+}
+
+/// `set_bonding_curve`, `None` clearing the curve
+pub fn set_bonding_curve(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, curve: Option<BondingCurve>) -> Instruction {
+    build(
+        accounts::SetBondingCurve {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            token_mint: *token_mint,
+            admin: *admin,
+        },
+        instruction::SetBondingCurve { curve },
+    )
+}
+
+/// `sell_tokens`, burning from the user's associated token account
+pub fn sell_tokens(
+    user: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    sell_amount: u64,
+    min_lamports_out: u64,
+) -> Instruction {
+    let (config, _) = pda::config_address(token_mint);
+    build(
+        accounts::SellTokens {
+            user: *user,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            token_mint: *token_mint,
+            user_token_account: get_associated_token_address_with_program_id(user, token_mint, token_program),
+            token_program: *token_program,
+        },
+        instruction::SellTokens { sell_amount, min_lamports_out },
+    )
+}
+
+/// `set_dutch_auction`, `None` clearing the auction
+pub fn set_dutch_auction(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, auction: Option<DutchAuction>) -> Instruction {
+    build(
+        accounts::SetDutchAuction {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            admin: *admin,
+        },
+        instruction::SetDutchAuction { auction },
+    )
+}
+
+/// `current_price`, a view instruction: simulate it and decode the little-endian `u64` return data
+pub fn current_price(token_mint: &Pubkey, sale_phase: Option<u8>) -> Instruction {
+    let (config, _) = pda::config_address(token_mint);
+    build(
+        accounts::CurrentPrice {
+            config,
+            sale_phase: sale_phase.map(|phase_id| pda::sale_phase_address(&config, phase_id).0),
+        },
+        instruction::CurrentPrice {},
+    )
+}
+
+/// `quote_mint`, a view instruction: simulate it and decode the little-endian `u64` return data
+pub fn quote_mint(token_mint: &Pubkey, sale_phase: Option<u8>, mint_amount: u64) -> Instruction {
+    let (config, _) = pda::config_address(token_mint);
+    build(
+        accounts::CurrentPrice {
+            config,
+            sale_phase: sale_phase.map(|phase_id| pda::sale_phase_address(&config, phase_id).0),
+        },
+        instruction::QuoteMint { mint_amount },
+    )
+}
+
+/// `finalize_sale`, sweeping the treasury into `destination` when one is given
+pub fn finalize_sale(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    destination: Option<&Pubkey>,
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    build(
+        accounts::FinalizeSale {
+            fee_payer: *fee_payer,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            admin: *admin,
+            token_mint: *token_mint,
+            mint_authority: pda::mint_authority_address(&config).0,
+            destination: destination.copied(),
+            token_program: *token_program,
+        },
+        instruction::FinalizeSale {},
+    )
+}
+
+/// `set_payment_mints`, an empty list accepting lamports only
+pub fn set_payment_mints(fee_payer: &Pubkey, admin: &Pubkey, token_mint: &Pubkey, payment_mints: Vec<PaymentMint>) -> Instruction {
+    build(
+        accounts::SetPaymentMints {
+            fee_payer: *fee_payer,
+            config: pda::config_address(token_mint).0,
+            admin: *admin,
+        },
+        instruction::SetPaymentMints { payment_mints },
+    )
+}
+
+/// `mint_tokens_with_token`, paying from the user's associated token account for `payment_mint`
+pub fn mint_tokens_with_token(
+    mint: &MintAccounts,
+    payment_mint: &Pubkey,
+    payment_token_program: &Pubkey,
+    mint_amount: u64,
+    max_total_cost: u64,
+) -> Instruction {
+    let v2 = mint.mint_tokens_v2();
+    build(
+        accounts::MintTokensWithToken {
+            fee_payer: v2.fee_payer,
+            config: v2.config,
+            treasury: v2.treasury,
+            token_mint: v2.token_mint,
+            user: v2.user,
+            user_account: v2.user_account,
+            user_token_account: v2.user_token_account,
+            mint_authority: v2.mint_authority,
+            token_program: v2.token_program,
+            associated_token_program: v2.associated_token_program,
+            system_program: v2.system_program,
+            payment_mint: *payment_mint,
+            user_payment_account: get_associated_token_address_with_program_id(&mint.user, payment_mint, payment_token_program),
+            treasury_payment_account: pda::treasury_payment_account(&v2.config, payment_mint, payment_token_program),
+            payment_token_program: *payment_token_program,
+            sale_phase: v2.sale_phase,
+        },
+        instruction::MintTokensWithToken { mint_amount, max_total_cost },
+    )
+}
+
+/// `mint_tokens_with_token` capped at the quote for a freshly fetched configuration
+pub fn quoted_mint_tokens_with_token(
+    mint: &MintAccounts,
+    config: &NsdConfig,
+    payment_mint: &Pubkey,
+    payment_token_program: &Pubkey,
+    mint_amount: u64,
+) -> Result<Instruction> {
+    let max_total_cost = quote::token_cost(config, payment_mint, mint_amount)?;
+    Ok(mint_tokens_with_token(mint, payment_mint, payment_token_program, mint_amount, max_total_cost))
+}
+
+/// `withdraw_payment_tokens` from the treasury token account for `payment_mint` into `destination`
+pub fn withdraw_payment_tokens(
+    fee_payer: &Pubkey,
+    admin: &Pubkey,
+    token_mint: &Pubkey,
+    payment_mint: &Pubkey,
+    payment_token_program: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let config = pda::config_address(token_mint).0;
+    build(
+        accounts::WithdrawPaymentTokens {
+            fee_payer: *fee_payer,
+            config,
+            treasury: pda::treasury_address(&config).0,
+            admin: *admin,
+            payment_mint: *payment_mint,
+            treasury_payment_account: pda::treasury_payment_account(&config, payment_mint, payment_token_program),
+            destination: *destination,
+            payment_token_program: *payment_token_program,
+        },
+        instruction::WithdrawPaymentTokens { amount },
+    )
+}
@@ -0,0 +1,17 @@
+//! NSD Minting Client - Rust SDK for the NSD Minting Program
+//!
+//! This crate builds instructions for the NSD minting program and decodes its accounts
+//! for off-chain services. It depends on the program crate with the `no-entrypoint` feature,
+//! so account layouts, instruction data and PDA seeds always match the deployed program.
+//!
+//! Every sale is addressed by its token mint: builders take the mint and derive the
+//! configuration and the other PDAs from it.
+
+// Import required modules and macros
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use nsd_minting::constants;
+pub use nsd_minting::{BondingCurve, DutchAuction, NsdConfig, NsdTokenMetadata, NsdTreasury, NsdUser, PaymentMint, PriceTier, SalePhase, UpdateConfigArgs, ID};
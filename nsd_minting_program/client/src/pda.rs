@@ -0,0 +1,55 @@
+//! NSD Minting Client - Rust SDK for the NSD Minting Program
+//!
+//! PDA derivation for the accounts of a sale. The seeds are the constants exported by the
+//! program crate, so these helpers cannot drift from the on-chain constraints.
+
+// Import required modules and macros
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::metadata::mpl_token_metadata;
+use nsd_minting::constants::*;
+
+/// Configuration PDA of the sale for `token_mint`
+pub fn config_address(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED, token_mint.as_ref()], &nsd_minting::ID)
+}
+
+/// Minting history PDA of `user` within a configuration
+pub fn user_address(config: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_SEED, config.as_ref(), user.as_ref()], &nsd_minting::ID)
+}
+
+/// Treasury PDA collecting the mint proceeds of a configuration
+pub fn treasury_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED, config.as_ref()], &nsd_minting::ID)
+}
+
+/// Mint authority PDA of a configuration
+pub fn mint_authority_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, config.as_ref()], &nsd_minting::ID)
+}
+
+/// Freeze authority PDA of a configuration
+pub fn freeze_authority_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FREEZE_AUTHORITY_SEED, config.as_ref()], &nsd_minting::ID)
+}
+
+/// Token metadata PDA of the sale for `token_mint`
+pub fn metadata_address(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[METADATA_SEED, token_mint.as_ref()], &nsd_minting::ID)
+}
+
+/// Sale phase PDA with the given id within a configuration
+pub fn sale_phase_address(config: &Pubkey, phase_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SALE_PHASE_SEED, config.as_ref(), &phase_id.to_le_bytes()], &nsd_minting::ID)
+}
+
+/// Metaplex metadata account of `token_mint`, owned by the Token Metadata program
+pub fn metaplex_metadata_address(token_mint: &Pubkey) -> (Pubkey, u8) {
+    mpl_token_metadata::accounts::Metadata::find_pda(token_mint)
+}
+
+/// Treasury token account collecting payments in `payment_mint`, the treasury PDA's associated token account
+pub fn treasury_payment_account(config: &Pubkey, payment_mint: &Pubkey, payment_token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(&treasury_address(config).0, payment_mint, payment_token_program)
+}
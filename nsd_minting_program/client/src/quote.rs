@@ -0,0 +1,37 @@
+//! NSD Minting Client - Rust SDK for the NSD Minting Program
+//!
+//! Price quotes. These run the program's own pricing code on fetched accounts,
+//! so a quote is exactly the amount the next mint would be charged.
+
+// Import required modules and macros
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Result;
+use nsd_minting::{NsdConfig, SalePhase};
+
+/// Lamports charged for minting `mint_amount` tokens at `now`
+///
+/// `phase` is the sale phase the mint would be sent with, if any. Fails the same way as the
+/// program when phases are configured and none is active at `now`. Pass the quote, or a
+/// slightly higher value, as the mint's `max_total_cost`.
+pub fn mint_cost(config: &NsdConfig, phase: Option<&SalePhase>, mint_amount: u64, now: i64) -> Result<u64> {
+    let phase = config.active_phase(phase, now)?;
+    config.quote(phase, mint_amount, now)
+}
+
+/// Lamports charged for the next token at `now`, as returned by the `current_price` view instruction
+pub fn current_price(config: &NsdConfig, phase: Option<&SalePhase>, now: i64) -> Result<u64> {
+    let phase = config.active_phase(phase, now)?;
+    config.unit_price(phase, now)
+}
+
+/// Lamports refunded for selling `sell_amount` tokens back along the bonding curve
+///
+/// Pass a slightly lower value as `min_lamports_out` to tolerate other trades landing first.
+pub fn sell_refund(config: &NsdConfig, sell_amount: u64) -> Result<u64> {
+    config.quote_sell(sell_amount)
+}
+
+/// Base units of `payment_mint` charged for minting `mint_amount` tokens with `mint_tokens_with_token`
+pub fn token_cost(config: &NsdConfig, payment_mint: &Pubkey, mint_amount: u64) -> Result<u64> {
+    config.quote_token(payment_mint, mint_amount)
+}
@@ -0,0 +1,251 @@
+//! NSD Minting Client - Rust SDK for the NSD Minting Program
+//!
+//! Client tests: PDAs, instruction layouts and account decoding. These run natively with `cargo test`.
+
+// Import required modules and macros
+use anchor_lang::{AccountSerialize, Discriminator, InstructionData};
+use anchor_lang::prelude::Pubkey;
+use nsd_minting_client::{accounts, instructions, pda, quote, BondingCurve, DutchAuction, NsdConfig, NsdUser, PaymentMint, PriceTier, UpdateConfigArgs};
+
+fn config_fixture(token_mint: Pubkey) -> NsdConfig {
+    NsdConfig {
+        admin: Pubkey::new_unique(),
+        token_mint,
+        max_supply: 1_000,
+        mint_price: 500,
+        total_minted: 10,
+        is_active: true,
+        bump: 255,
+        total_revenue: 5_000,
+        total_withdrawn: 0,
+        treasury_bump: 254,
+        mint_authority_bound: true,
+        mint_authority_bump: 253,
+        decimals: 0,
+        freeze_authority_bump: 252,
+        max_per_wallet: Some(5),
+        max_per_transaction: u64::MAX,
+        cooldown_seconds: 0,
+        allowlist_root: None,
+        phase_count: 0,
+        pending_admin: None,
+        min_price: None,
+        max_price: None,
+        max_supply_frozen: false,
+        is_finalized: false,
+        price_tiers: vec![],
+        bonding_curve: None,
+        total_refunded: 0,
+        dutch_auction: None,
+        payment_mints: vec![],
+    }
+}
+
+#[test]
+fn pdas_use_the_program_seeds() {
+    let token_mint = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let (config, _) = pda::config_address(&token_mint);
+
+    let expected = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &nsd_minting::ID).0;
+    assert_eq!(config, expected(&[b"nsd_config", token_mint.as_ref()]));
+    assert_eq!(pda::user_address(&config, &user).0, expected(&[b"nsd_user", config.as_ref(), user.as_ref()]));
+    assert_eq!(pda::treasury_address(&config).0, expected(&[b"treasury", config.as_ref()]));
+    assert_eq!(pda::mint_authority_address(&config).0, expected(&[b"mint_authority", config.as_ref()]));
+    assert_eq!(pda::freeze_authority_address(&config).0, expected(&[b"freeze_authority", config.as_ref()]));
+    assert_eq!(pda::metadata_address(&token_mint).0, expected(&[b"metadata", token_mint.as_ref()]));
+    assert_eq!(pda::sale_phase_address(&config, 3).0, expected(&[b"sale_phase", config.as_ref(), &[3]]));
+}
+
+#[test]
+fn mint_builders_fill_in_the_sale_accounts() {
+    let payer = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+    let (config, _) = pda::config_address(&token_mint);
+    let mint = instructions::MintAccounts {
+        fee_payer: payer,
+        user: payer,
+        token_mint,
+        token_program: anchor_spl::token::ID,
+        sale_phase: Some(0),
+    };
+
+    let instruction = instructions::mint_tokens_v2(&mint, 7, 3_500);
+    assert_eq!(instruction.program_id, nsd_minting::ID);
+    assert_eq!(instruction.data, nsd_minting::instruction::MintTokensV2 { mint_amount: 7, max_total_cost: 3_500 }.data());
+    let keys: Vec<Pubkey> = instruction.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(keys[1], config);
+    assert_eq!(keys[2], pda::treasury_address(&config).0);
+    assert_eq!(keys[5], pda::user_address(&config, &payer).0);
+    assert_eq!(keys[11], pda::sale_phase_address(&config, 0).0);
+
+    // Without a phase the optional account is the program id placeholder
+    let instruction = instructions::presale_mint(&instructions::MintAccounts { sale_phase: None, ..mint }, 1, 500, 2, vec![]);
+    assert!(instruction.data.starts_with(nsd_minting::instruction::PresaleMint::DISCRIMINATOR));
+    assert_eq!(instruction.accounts[11].pubkey, nsd_minting::ID);
+}
+
+#[test]
+fn quoted_mints_cap_the_cost_at_the_quote() {
+    let payer = Pubkey::new_unique();
+    let mut config = config_fixture(Pubkey::new_unique());
+    let mint = instructions::MintAccounts {
+        fee_payer: payer,
+        user: payer,
+        token_mint: config.token_mint,
+        token_program: anchor_spl::token::ID,
+        sale_phase: None,
+    };
+
+    let instruction = instructions::quoted_mint_tokens(&mint, &config, None, 4, 0).unwrap();
+    assert_eq!(instruction.data, nsd_minting::instruction::MintTokens { mint_amount: 4, max_total_cost: 4 * 500 }.data());
+
+    // The quote follows the live pricing, here a bonding curve past 10 sold tokens
+    config.bonding_curve = Some(BondingCurve { base_price: 100, slope: 10 });
+    let instruction = instructions::quoted_mint_tokens_v2(&mint, &config, None, 2, 0).unwrap();
+    assert_eq!(instruction.data, nsd_minting::instruction::MintTokensV2 { mint_amount: 2, max_total_cost: 200 + 210 }.data());
+
+    // Phases configured but none active: nothing to quote
+    config.phase_count = 1;
+    assert!(instructions::quoted_mint_tokens_v2(&mint, &config, None, 2, 0).is_err());
+}
+
+#[test]
+fn metadata_builders_pick_accounts_by_token_program() {
+    let payer = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+
+    let spl = instructions::lock_metadata(&payer, &payer, &token_mint, &anchor_spl::token::ID);
+    assert_eq!(spl.accounts[2].pubkey, pda::metadata_address(&token_mint).0);
+    assert_eq!(spl.accounts[6].pubkey, pda::metaplex_metadata_address(&token_mint).0);
+
+    // Token-2022 mints keep their metadata in the mint, without the metadata account
+    let token_2022 = instructions::lock_metadata(&payer, &payer, &token_mint, &anchor_spl::token_2022::ID);
+    assert_eq!(token_2022.accounts[2].pubkey, nsd_minting::ID);
+    assert_eq!(token_2022.accounts[6].pubkey, nsd_minting::ID);
+    assert_eq!(token_2022.accounts[7].pubkey, nsd_minting::ID);
+}
+
+#[test]
+fn update_config_encodes_its_arguments() {
+    let payer = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+    let args = UpdateConfigArgs { mint_price: Some(42), ..Default::default() };
+
+    let instruction = instructions::update_config(&payer, &payer, &token_mint, args.clone());
+    assert_eq!(instruction.data, nsd_minting::instruction::UpdateConfig { args }.data());
+    assert_eq!(instruction.accounts[1].pubkey, pda::config_address(&token_mint).0);
+}
+
+#[test]
+fn quotes_split_a_mint_across_price_tiers() {
+    let mut config = config_fixture(Pubkey::new_unique());
+    assert_eq!(quote::mint_cost(&config, None, 4, 0).unwrap(), 4 * 500);
+
+    // 10 tokens already sold: 5 left at 100, the next 10 at 200, then 300
+    config.price_tiers = vec![
+        PriceTier { start_supply: 0, price: 100 },
+        PriceTier { start_supply: 15, price: 200 },
+        PriceTier { start_supply: 25, price: 300 },
+    ];
+    assert_eq!(quote::mint_cost(&config, None, 5, 0).unwrap(), 5 * 100);
+    assert_eq!(quote::mint_cost(&config, None, 20, 0).unwrap(), 5 * 100 + 10 * 200 + 5 * 300);
+
+    let tiers = config.price_tiers.clone();
+    let instruction = instructions::set_price_tiers(&config.admin, &config.admin, &config.token_mint, tiers.clone());
+    assert_eq!(instruction.data, nsd_minting::instruction::SetPriceTiers { tiers }.data());
+}
+
+#[test]
+fn bonding_curve_refunds_what_minting_cost() {
+    let mut config = config_fixture(Pubkey::new_unique());
+    assert!(quote::sell_refund(&config, 1).is_err());
+
+    // Token n costs 100 + 10n, and 10 tokens are already sold
+    config.bonding_curve = Some(BondingCurve { base_price: 100, slope: 10 });
+    let cost = quote::mint_cost(&config, None, 3, 0).unwrap();
+    assert_eq!(cost, 200 + 210 + 220);
+
+    config.total_minted += 3;
+    assert_eq!(quote::sell_refund(&config, 3).unwrap(), cost);
+    assert_eq!(quote::sell_refund(&config, 13).unwrap(), (0..13).map(|n| 100 + 10 * n).sum::<u64>());
+    assert!(quote::sell_refund(&config, 14).is_err());
+
+    let user = Pubkey::new_unique();
+    let instruction = instructions::sell_tokens(&user, &config.token_mint, &anchor_spl::token::ID, 3, cost);
+    assert_eq!(instruction.data, nsd_minting::instruction::SellTokens { sell_amount: 3, min_lamports_out: cost }.data());
+    assert_eq!(instruction.accounts[2].pubkey, pda::treasury_address(&pda::config_address(&config.token_mint).0).0);
+}
+
+#[test]
+fn dutch_auction_prices_decay_to_the_floor() {
+    let mut config = config_fixture(Pubkey::new_unique());
+    config.dutch_auction = Some(DutchAuction { start_price: 1_000, floor_price: 200, start_timestamp: 100, decay_interval: 80 });
+
+    assert_eq!(quote::current_price(&config, None, 0).unwrap(), 1_000);
+    assert_eq!(quote::current_price(&config, None, 130).unwrap(), 700);
+    assert_eq!(quote::current_price(&config, None, 180).unwrap(), 200);
+    assert_eq!(quote::current_price(&config, None, i64::MAX).unwrap(), 200);
+    assert_eq!(quote::mint_cost(&config, None, 3, 130).unwrap(), 3 * 700);
+
+    let instruction = instructions::current_price(&config.token_mint, None);
+    assert!(instruction.accounts.iter().all(|meta| !meta.is_writable && !meta.is_signer));
+    assert_eq!(instruction.accounts[1].pubkey, nsd_minting::ID);
+
+    let instruction = instructions::quote_mint(&config.token_mint, None, 3);
+    assert_eq!(instruction.data, nsd_minting::instruction::QuoteMint { mint_amount: 3 }.data());
+    assert!(instruction.accounts.iter().all(|meta| !meta.is_writable && !meta.is_signer));
+}
+
+#[test]
+fn token_payments_use_the_treasury_token_account() {
+    let mut config = config_fixture(Pubkey::new_unique());
+    let usdc = Pubkey::new_unique();
+    assert!(quote::token_cost(&config, &usdc, 1).is_err());
+
+    config.payment_mints = vec![PaymentMint { mint: usdc, price: 2_500_000 }];
+    assert_eq!(quote::token_cost(&config, &usdc, 4).unwrap(), 10_000_000);
+
+    let user = Pubkey::new_unique();
+    let mint = instructions::MintAccounts {
+        fee_payer: user,
+        user,
+        token_mint: config.token_mint,
+        token_program: anchor_spl::token::ID,
+        sale_phase: None,
+    };
+    let instruction = instructions::quoted_mint_tokens_with_token(&mint, &config, &usdc, &anchor_spl::token::ID, 4).unwrap();
+    assert_eq!(instruction.data, nsd_minting::instruction::MintTokensWithToken { mint_amount: 4, max_total_cost: 10_000_000 }.data());
+
+    // The treasury PDA owns the associated token account the payments go to
+    let (config_address, _) = pda::config_address(&config.token_mint);
+    let treasury_account = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &pda::treasury_address(&config_address).0,
+        &usdc,
+        &anchor_spl::token::ID,
+    );
+    assert_eq!(instruction.accounts[12].pubkey, anchor_spl::associated_token::get_associated_token_address(&user, &usdc));
+    assert_eq!(instruction.accounts[13].pubkey, treasury_account);
+
+    let destination = Pubkey::new_unique();
+    let withdraw = instructions::withdraw_payment_tokens(&config.admin, &config.admin, &config.token_mint, &usdc, &anchor_spl::token::ID, &destination, 7);
+    assert_eq!(withdraw.accounts[5].pubkey, treasury_account);
+    assert_eq!(withdraw.data, nsd_minting::instruction::WithdrawPaymentTokens { amount: 7 }.data());
+}
+
+#[test]
+fn deserializers_check_the_account_type() {
+    let config = config_fixture(Pubkey::new_unique());
+    let mut data = Vec::new();
+    config.try_serialize(&mut data).unwrap();
+
+    let decoded = accounts::deserialize_config(&data).unwrap();
+    assert_eq!(decoded.token_mint, config.token_mint);
+    assert_eq!(decoded.max_per_wallet, Some(5));
+
+    assert!(accounts::deserialize_user(&data).is_err());
+    let user = NsdUser { user: Pubkey::new_unique(), tokens_minted: 3, last_mint_timestamp: 0, bump: 250 };
+    let mut data = Vec::new();
+    user.try_serialize(&mut data).unwrap();
+    assert_eq!(accounts::deserialize_user(&data).unwrap().tokens_minted, 3);
+}
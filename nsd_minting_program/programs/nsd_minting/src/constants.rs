@@ -2,3 +2,51 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Maximum length in bytes of the token name
+#[constant]
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Maximum length in bytes of the token symbol
+#[constant]
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+
+/// Maximum length in bytes of the token metadata URI
+#[constant]
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// Seed of the configuration PDA, followed by the token mint
+#[constant]
+pub const CONFIG_SEED: &[u8] = b"nsd_config";
+
+/// Seed of the user PDA, followed by the configuration and the user
+#[constant]
+pub const USER_SEED: &[u8] = b"nsd_user";
+
+/// Seed of the treasury PDA, followed by the configuration
+#[constant]
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// Seed of the mint authority PDA, followed by the configuration
+#[constant]
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+
+/// Seed of the freeze authority PDA, followed by the configuration
+#[constant]
+pub const FREEZE_AUTHORITY_SEED: &[u8] = b"freeze_authority";
+
+/// Seed of the token metadata PDA, followed by the token mint
+#[constant]
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+/// Seed of the sale phase PDA, followed by the configuration and the phase id
+#[constant]
+pub const SALE_PHASE_SEED: &[u8] = b"sale_phase";
+
+/// Maximum number of entries in a sale's price-tier table
+#[constant]
+pub const MAX_PRICE_TIERS: usize = 8;
+
+/// Maximum number of SPL tokens a sale accepts as payment
+#[constant]
+pub const MAX_PAYMENT_MINTS: usize = 4;
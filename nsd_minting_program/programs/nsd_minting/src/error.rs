@@ -31,4 +31,180 @@ pub enum NsdError {
     /// Error thrown when an unauthorized account tries to perform admin operations
     #[msg("Unauthorized to perform this action")]
     Unauthorized,
-}
\ No newline at end of file
+    
+    /// Error thrown when a withdrawal would take the treasury below its rent-exempt minimum
+    #[msg("Insufficient treasury balance")]
+    InsufficientTreasuryBalance,
+    
+    /// Error thrown when minting before the mint authority has been bound to the program PDA
+    #[msg("Mint authority has not been bound to the program")]
+    MintAuthorityNotBound,
+    
+    /// Error thrown when the token mint's authority is not the program's mint authority PDA
+    #[msg("Token mint authority is not the program mint authority")]
+    InvalidMintAuthority,
+    
+    /// Error thrown when a mint account does not match the mint stored in the configuration
+    #[msg("Token mint does not match the configured mint")]
+    InvalidTokenMint,
+    
+    /// Error thrown when the recipient token account does not belong to the minting user
+    #[msg("Recipient token account does not belong to the user")]
+    InvalidRecipient,
+    
+    /// Error thrown when a supply, cost or accounting calculation overflows
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    
+    /// Error thrown when a single mint asks for more tokens than the per-transaction limit
+    #[msg("Exceeds maximum tokens per transaction")]
+    ExceedsTransactionLimit,
+    
+    /// Error thrown when a mint would take the wallet past its per-wallet cap
+    #[msg("Exceeds maximum tokens per wallet")]
+    ExceedsWalletLimit,
+    
+    /// Error thrown when a wallet mints again before its cooldown has elapsed
+    #[msg("Mint cooldown has not elapsed")]
+    MintCooldownActive,
+    
+    /// Error thrown when presale minting is attempted while no allowlist root is configured
+    #[msg("Presale is not currently active")]
+    PresaleNotActive,
+    
+    /// Error thrown when the allowlist proof does not match the configured root
+    #[msg("Invalid allowlist proof")]
+    InvalidMerkleProof,
+    
+    /// Error thrown when a presale mint would exceed the wallet's allocation
+    #[msg("Exceeds presale allocation")]
+    ExceedsAllocation,
+    
+    /// Error thrown when minting outside the window of every configured sale phase
+    #[msg("No sale phase is active at this time")]
+    NoActiveSalePhase,
+    
+    /// Error thrown when a public mint is attempted during an allowlist-only sale phase
+    #[msg("The active sale phase requires an allowlist proof")]
+    AllowlistRequired,
+    
+    /// Error thrown when a sale phase's start is not before its end
+    #[msg("Sale phase must start before it ends")]
+    InvalidPhaseWindow,
+    
+    /// Error thrown when accepting or cancelling an admin handover that was never proposed
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    
+    /// Error thrown when a key other than the proposed admin tries to accept the handover
+    #[msg("Signer is not the pending admin")]
+    InvalidPendingAdmin,
+    
+    /// Error thrown when the token name is longer than `MAX_NAME_LENGTH` bytes
+    #[msg("Token name is too long")]
+    NameTooLong,
+    
+    /// Error thrown when the token symbol is longer than `MAX_SYMBOL_LENGTH` bytes
+    #[msg("Token symbol is too long")]
+    SymbolTooLong,
+    
+    /// Error thrown when the metadata URI is longer than `MAX_URI_LENGTH` bytes
+    #[msg("Metadata URI is too long")]
+    UriTooLong,
+    
+    /// Error thrown when changing metadata after it has been locked
+    #[msg("Token metadata is locked")]
+    MetadataLocked,
+    
+    /// Error thrown when the metadata account or the Metaplex metadata accounts are missing for an SPL Token mint
+    #[msg("Metadata and Metaplex metadata accounts are required for SPL Token mints")]
+    MissingMetadataAccounts,
+    
+    /// Error thrown when the maximum supply would fall below the tokens already minted
+    #[msg("Maximum supply is below the total minted")]
+    MaxSupplyBelowMinted,
+    
+    /// Error thrown when the configured minimum price is above the maximum price
+    #[msg("Minimum price exceeds maximum price")]
+    InvalidPriceBand,
+    
+    /// Error thrown when a mint or phase price lies outside the configured price band
+    #[msg("Price is outside the configured price band")]
+    PriceOutOfBand,
+    
+    /// Error thrown when raising the maximum supply after it has been frozen
+    #[msg("Maximum supply is frozen")]
+    MaxSupplyFrozen,
+    
+    /// Error thrown when minting or changing the configuration after the sale was finalized
+    #[msg("Sale has been finalized")]
+    SaleFinalized,
+    
+    /// Error thrown when setting more price tiers than a configuration holds
+    #[msg("Too many price tiers")]
+    TooManyPriceTiers,
+    
+    /// Error thrown when price tiers do not start at zero or their start supplies do not ascend
+    #[msg("Price tiers must start at zero and ascend")]
+    InvalidPriceTiers,
+    
+    /// Error thrown when more than one of price tiers, a bonding curve and a Dutch auction are set
+    #[msg("Only one of price tiers, a bonding curve and a Dutch auction can be set")]
+    PricingModeConflict,
+    
+    /// Error thrown when selling tokens while no bonding curve is set
+    #[msg("No bonding curve is set")]
+    BondingCurveNotSet,
+    
+    /// Error thrown when selling more tokens than the curve supply
+    #[msg("Sell amount exceeds the minted supply")]
+    ExceedsCurveSupply,
+    
+    /// Error thrown when a sell would refund less than the seller's minimum
+    #[msg("Refund is below the minimum requested")]
+    SlippageExceeded,
+    
+    /// Error thrown when a mint would cost more than the buyer's maximum
+    #[msg("Total cost exceeds the maximum requested")]
+    PriceExceedsLimit,
+    
+    /// Error thrown when a Dutch auction's floor price is above its start price
+    #[msg("Dutch auction floor price exceeds start price")]
+    InvalidDutchAuction,
+    
+    /// Error thrown when setting more payment mints than a configuration holds
+    #[msg("Too many payment mints")]
+    TooManyPaymentMints,
+    
+    /// Error thrown when the same payment mint is listed twice
+    #[msg("Payment mint listed more than once")]
+    DuplicatePaymentMint,
+    
+    /// Error thrown when paying with a token the sale does not accept
+    #[msg("Payment mint not accepted")]
+    PaymentMintNotAccepted,
+    
+    /// Error thrown when payment mints and a bonding curve are set together
+    #[msg("A bonding curve sale only accepts lamports")]
+    CurveRequiresLamports,
+    
+    /// Error thrown when setting, changing or clearing the bonding curve after tokens were minted
+    #[msg("Bonding curve can only change before any token is minted")]
+    BondingCurveLocked,
+    
+    /// Error thrown when a sale phase window overlaps the window of another phase
+    #[msg("Sale phase overlaps another phase")]
+    OverlappingSalePhases,
+    
+    /// Error thrown when the other sale phases passed to check for overlaps are missing, repeated or foreign
+    #[msg("Every other sale phase of the configuration must be passed once")]
+    InvalidPhaseAccounts,
+    
+    /// Error thrown when the metadata account is passed for a Token-2022 mint, which keeps its metadata in the mint
+    #[msg("The metadata account is only used by SPL Token mints")]
+    UnexpectedMetadataAccount,
+    
+    /// Error thrown when the mint's supply includes tokens the sale never minted, which no curve reserve backs
+    #[msg("Mint supply includes tokens minted outside the sale")]
+    UnbackedMintSupply,
+}
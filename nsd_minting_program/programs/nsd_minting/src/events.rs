@@ -0,0 +1,368 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::state::{BondingCurve, DutchAuction, PaymentMint, PriceTier};
+use anchor_lang::prelude::*;
+
+/// Event emitted when the admin proposes a new admin
+/// 
+/// The proposed admin only takes over once it accepts the handover.
+#[event]
+pub struct AdminProposed {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The current admin authority
+    pub admin: Pubkey,
+    
+    /// The proposed admin authority
+    pub pending_admin: Pubkey,
+}
+
+/// Event emitted when the admin cancels a pending handover
+#[event]
+pub struct AdminTransferCancelled {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The current admin authority
+    pub admin: Pubkey,
+    
+    /// The admin authority whose proposal was withdrawn
+    pub pending_admin: Pubkey,
+}
+
+/// Event emitted when the pending admin accepts the handover
+#[event]
+pub struct AdminTransferred {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The previous admin authority
+    pub old_admin: Pubkey,
+    
+    /// The new admin authority
+    pub new_admin: Pubkey,
+}
+
+/// Snapshot of the admin-settable sale parameters
+/// 
+/// Carried by `ConfigUpdated` so listeners can see both the old and the new values.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ConfigSettings {
+    /// Maximum supply of NSD tokens
+    pub max_supply: u64,
+    
+    /// Price per NSD token in lamports
+    pub mint_price: u64,
+    
+    /// Whether minting is active
+    pub is_active: bool,
+    
+    /// Per-wallet mint cap, if any
+    pub max_per_wallet: Option<u64>,
+    
+    /// Maximum number of tokens per mint transaction
+    pub max_per_transaction: u64,
+    
+    /// Minimum number of seconds between two mints from the same wallet
+    pub cooldown_seconds: u64,
+    
+    /// Merkle root of the presale allowlist, if any
+    pub allowlist_root: Option<[u8; 32]>,
+}
+
+/// Event emitted when a configuration is initialized
+#[event]
+pub struct ConfigInitialized {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority
+    pub admin: Pubkey,
+    
+    /// The NSD token mint
+    pub token_mint: Pubkey,
+    
+    /// Maximum supply of NSD tokens
+    pub max_supply: u64,
+    
+    /// Price per NSD token in lamports
+    pub mint_price: u64,
+}
+
+/// Event emitted when NSD tokens are minted
+#[event]
+pub struct TokensMinted {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The user who received the tokens
+    pub user: Pubkey,
+    
+    /// Number of tokens minted
+    pub amount: u64,
+    
+    /// Total price paid in lamports
+    pub price_paid: u64,
+    
+    /// Total number of tokens minted after this mint
+    pub total_minted: u64,
+}
+
+/// Event emitted when the admin updates the configuration
+#[event]
+pub struct ConfigUpdated {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority who made the change
+    pub admin: Pubkey,
+    
+    /// The settings before the update
+    pub old: ConfigSettings,
+    
+    /// The settings after the update
+    pub new: ConfigSettings,
+}
+
+/// Event emitted when token metadata is set
+#[event]
+pub struct MetadataSet {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The token mint the metadata describes
+    pub mint: Pubkey,
+    
+    /// Name of the token
+    pub name: String,
+    
+    /// Symbol of the token
+    pub symbol: String,
+    
+    /// URI for token metadata
+    pub uri: String,
+}
+
+/// Event emitted when lamports are withdrawn from the treasury
+#[event]
+pub struct TreasuryWithdrawn {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The account receiving the lamports
+    pub destination: Pubkey,
+    
+    /// Number of lamports withdrawn
+    pub amount: u64,
+    
+    /// Total lamports withdrawn after this withdrawal
+    pub total_withdrawn: u64,
+}
+
+/// Event emitted when the mint authority PDA is bound to the configuration
+#[event]
+pub struct MintAuthorityBound {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The NSD token mint
+    pub token_mint: Pubkey,
+    
+    /// The program PDA holding the mint authority
+    pub mint_authority: Pubkey,
+}
+
+/// Event emitted when a sale phase is created or updated
+#[event]
+pub struct SalePhaseSet {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// Identifier of the phase within the configuration
+    pub phase_id: u8,
+    
+    /// Unix timestamp at which the phase opens
+    pub start_timestamp: i64,
+    
+    /// Unix timestamp at which the phase closes
+    pub end_timestamp: i64,
+    
+    /// Price per NSD token in lamports during the phase
+    pub price: u64,
+    
+    /// Per-wallet mint cap during the phase, if any
+    pub max_per_wallet: Option<u64>,
+    
+    /// Merkle root of the phase allowlist, if any
+    pub allowlist_root: Option<[u8; 32]>,
+}
+
+/// Event emitted when a sale phase is closed
+#[event]
+pub struct SalePhaseClosed {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// Identifier of the closed phase
+    pub phase_id: u8,
+}
+
+/// Event emitted when token metadata is permanently locked
+#[event]
+pub struct MetadataLocked {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The token mint the metadata describes
+    pub mint: Pubkey,
+}
+
+/// Event emitted when the maximum supply is permanently frozen
+#[event]
+pub struct MaxSupplyFrozen {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority that froze the supply
+    pub admin: Pubkey,
+    
+    /// The maximum supply at the time it was frozen
+    pub max_supply: u64,
+}
+
+/// Event emitted when the admin replaces the price tiers
+#[event]
+pub struct PriceTiersSet {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority who set the tiers
+    pub admin: Pubkey,
+    
+    /// The new price tiers, empty when flat pricing was restored
+    pub tiers: Vec<PriceTier>,
+}
+
+/// Event emitted when the admin sets or clears the bonding curve
+#[event]
+pub struct BondingCurveSet {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority who set the curve
+    pub admin: Pubkey,
+    
+    /// The new bonding curve, `None` when it was cleared
+    pub curve: Option<BondingCurve>,
+}
+
+/// Event emitted when the admin sets or clears the Dutch auction
+#[event]
+pub struct DutchAuctionSet {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority who set the auction
+    pub admin: Pubkey,
+    
+    /// The new Dutch auction, `None` when it was cleared
+    pub auction: Option<DutchAuction>,
+}
+
+/// Event emitted when a user sells tokens back along the bonding curve
+#[event]
+pub struct TokensSold {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The user who sold the tokens
+    pub user: Pubkey,
+    
+    /// Number of tokens burned
+    pub amount: u64,
+    
+    /// Lamports refunded from the treasury
+    pub refund: u64,
+    
+    /// Total number of tokens minted after this sell
+    pub total_minted: u64,
+}
+
+/// Event emitted when a sale is finalized and its mint authority revoked
+#[event]
+pub struct SaleFinalized {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority that finalized the sale
+    pub admin: Pubkey,
+    
+    /// The token mint whose mint authority was revoked
+    pub token_mint: Pubkey,
+    
+    /// Final number of tokens minted, which is now the fixed supply
+    pub total_minted: u64,
+    
+    /// Lamports swept from the treasury, zero when the treasury was kept
+    pub swept: u64,
+}
+
+/// Event emitted when the admin replaces the accepted payment mints
+#[event]
+pub struct PaymentMintsSet {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The admin authority who set the payment mints
+    pub admin: Pubkey,
+    
+    /// The new payment mints, empty when only lamports are accepted
+    pub payment_mints: Vec<PaymentMint>,
+}
+
+/// Event emitted when NSD tokens are minted against an SPL token payment
+#[event]
+pub struct TokensMintedWithToken {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The user who received the tokens
+    pub user: Pubkey,
+    
+    /// Number of tokens minted
+    pub amount: u64,
+    
+    /// The token paid with
+    pub payment_mint: Pubkey,
+    
+    /// Total price paid in base units of the payment token
+    pub price_paid: u64,
+    
+    /// Total number of tokens minted after this mint
+    pub total_minted: u64,
+}
+
+/// Event emitted when payment tokens are withdrawn from the treasury
+#[event]
+pub struct PaymentTokensWithdrawn {
+    /// The configuration account
+    pub config: Pubkey,
+    
+    /// The token withdrawn
+    pub payment_mint: Pubkey,
+    
+    /// The token account receiving the tokens
+    pub destination: Pubkey,
+    
+    /// Number of tokens withdrawn in base units
+    pub amount: u64,
+}
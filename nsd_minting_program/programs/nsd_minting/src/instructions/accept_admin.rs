@@ -0,0 +1,74 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for accepting the admin handover
+/// 
+/// This struct defines the accounts needed for the accept_admin instruction.
+/// It includes the fee payer, configuration account and the pending admin authority.
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The pending admin authority taking over
+    /// Must be a signer account
+    pub new_admin: Signer<'info>,
+}
+
+/// Accept the admin handover
+/// 
+/// This instruction makes the pending admin the admin of the configuration.
+/// It must be signed by the key recorded by `propose_admin`.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `new_admin` - Signer - The pending admin authority account
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<AcceptAdmin>,
+) -> Result<()> {
+    // Check the pending admin
+    // Verify that a handover was proposed to the signer
+    let pending_admin = ctx.accounts.config.pending_admin.ok_or(NsdError::NoPendingAdmin)?;
+    require!(pending_admin == ctx.accounts.new_admin.key(), NsdError::InvalidPendingAdmin);
+    
+    // Hand over the admin authority
+    let old_admin = ctx.accounts.config.admin;
+    ctx.accounts.config.admin = pending_admin;
+    ctx.accounts.config.pending_admin = None;
+    
+    emit!(AdminTransferred {
+        config: ctx.accounts.config.key(),
+        old_admin,
+        new_admin: pending_admin,
+    });
+    
+    Ok(())
+}
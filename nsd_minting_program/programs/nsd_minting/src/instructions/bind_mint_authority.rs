@@ -0,0 +1,108 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+
+use anchor_spl::token_interface::Mint;
+
+/// Accounts required for binding the mint authority PDA
+/// 
+/// This struct defines the accounts needed for the bind_mint_authority instruction.
+/// It includes the fee payer, configuration account, admin authority, token mint and mint authority PDA.
+#[derive(Accounts)]
+pub struct BindMintAuthority<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The admin authority account that can modify configuration
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The token mint account for NSD tokens
+    /// Must match the mint stored in the configuration
+    #[account(
+        address = config.token_mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The program PDA expected to hold the mint authority
+    /// Seeded with "mint_authority" and the config key
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
+}
+
+/// Bind the mint authority PDA to the configuration
+/// 
+/// This instruction verifies that the token mint's authority has been transferred to
+/// the program's mint authority PDA and records it in the configuration.
+/// Once bound, users can mint without any external co-signer.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `admin` - Signer - The admin authority account
+/// - `token_mint` - Mint - The NSD token mint account
+/// - `mint_authority` - PDA - The program's mint authority
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<BindMintAuthority>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the mint authority really is the program PDA
+    // The mint authority must already have been transferred to the PDA
+    require!(
+        ctx.accounts.token_mint.mint_authority == COption::Some(ctx.accounts.mint_authority.key()),
+        NsdError::InvalidMintAuthority
+    );
+    
+    // Bind the mint authority
+    // Mark the mint authority as bound
+    ctx.accounts.config.mint_authority_bound = true;
+    // Store the bump seed for the mint authority PDA
+    ctx.accounts.config.mint_authority_bump = ctx.bumps.mint_authority;
+    // Record the mint decimals
+    ctx.accounts.config.decimals = ctx.accounts.token_mint.decimals;
+    
+    emit!(MintAuthorityBound {
+        config: ctx.accounts.config.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        mint_authority: ctx.accounts.mint_authority.key(),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for cancelling the admin handover
+/// 
+/// This struct defines the accounts needed for the cancel_admin_transfer instruction.
+/// It includes the fee payer, configuration account and the current admin authority.
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The current admin authority account
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Cancel the admin handover
+/// 
+/// This instruction withdraws a pending admin proposal. The current admin stays in charge.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `admin` - Signer - The current admin authority account
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<CancelAdminTransfer>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Clear the pending admin
+    let pending_admin = ctx.accounts.config.pending_admin.take().ok_or(NsdError::NoPendingAdmin)?;
+    
+    emit!(AdminTransferCancelled {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        pending_admin,
+    });
+    
+    Ok(())
+}
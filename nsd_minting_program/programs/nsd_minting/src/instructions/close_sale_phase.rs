@@ -0,0 +1,87 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for closing a sale phase
+/// 
+/// This struct defines the accounts needed for the close_sale_phase instruction.
+/// It includes the fee payer, configuration account, the sale phase and the admin authority.
+#[derive(Accounts)]
+pub struct CloseSalePhase<'info> {
+    /// The fee payer for the transaction, receives the phase account's rent
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The sale phase account to close
+    /// Must belong to this configuration
+    #[account(
+        mut,
+        close = fee_payer,
+        seeds = [
+            SALE_PHASE_SEED,
+            config.key().as_ref(),
+            sale_phase.phase_id.to_le_bytes().as_ref(),
+        ],
+        bump = sale_phase.bump,
+    )]
+    pub sale_phase: Account<'info, SalePhase>,
+
+    /// The admin authority account that can manage sale phases
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Close a sale phase
+/// 
+/// This instruction closes a sale phase account and returns its rent to the fee payer.
+/// Once the last phase is closed, mints are no longer restricted to phase windows.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees and receiving the rent
+/// - `config` - Writable - The configuration account
+/// - `sale_phase` - Writable - The sale phase account to close
+/// - `admin` - Signer - The admin authority account
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<CloseSalePhase>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Stop counting the closed phase
+    ctx.accounts.config.phase_count = ctx.accounts.config.phase_count
+        .checked_sub(1)
+        .ok_or(NsdError::ArithmeticOverflow)?;
+    
+    emit!(SalePhaseClosed {
+        config: ctx.accounts.config.key(),
+        phase_id: ctx.accounts.sale_phase.phase_id,
+    });
+    
+    Ok(())
+}
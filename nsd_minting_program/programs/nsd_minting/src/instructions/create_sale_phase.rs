@@ -0,0 +1,161 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for creating a sale phase
+/// 
+/// This struct defines the accounts needed for the create_sale_phase instruction.
+/// It includes the fee payer, configuration account, the new sale phase and the admin authority.
+#[derive(Accounts)]
+#[instruction(
+    phase_id: u8,
+    start_timestamp: i64,
+    end_timestamp: i64,
+    price: u64,
+    max_per_wallet: Option<u64>,
+    allowlist_root: Option<[u8; 32]>,
+)]
+pub struct CreateSalePhase<'info> {
+    /// The fee payer for account initialization
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The sale phase account to create
+    /// This account is initialized with specific space and seeds
+    #[account(
+        init,
+        space=108,
+        payer=fee_payer,
+        seeds = [
+            SALE_PHASE_SEED,
+            config.key().as_ref(),
+            phase_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub sale_phase: Account<'info, SalePhase>,
+
+    /// The admin authority account that can manage sale phases
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The system program account for account initialization
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a time-boxed sale phase
+/// 
+/// This instruction creates a sale phase with its own window, price and limits.
+/// Once any phase exists, mints are only accepted inside an active phase window.
+/// The window may not overlap the window of any existing phase.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for account initialization
+/// - `config` - Writable - The configuration account
+/// - `sale_phase` - Writable - The sale phase account to be initialized
+/// - `admin` - Signer - The admin authority account
+/// - `system_program` - System program - Required for account initialization
+/// - Remaining accounts - Every existing sale phase of the configuration, to check for overlaps
+/// 
+/// # Parameters
+/// - `phase_id` - Identifier of the phase within the configuration
+/// - `start_timestamp` - Unix timestamp at which the phase opens
+/// - `end_timestamp` - Unix timestamp at which the phase closes
+/// - `price` - Price per NSD token in lamports during the phase
+/// - `max_per_wallet` - Per-wallet mint cap during the phase (optional)
+/// - `allowlist_root` - Allowlist Merkle root restricting the phase to presale mints (optional)
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<CreateSalePhase>,
+    phase_id: u8,
+    start_timestamp: i64,
+    end_timestamp: i64,
+    price: u64,
+    max_per_wallet: Option<u64>,
+    allowlist_root: Option<[u8; 32]>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Check the phase window
+    // The phase must open before it closes
+    require!(start_timestamp < end_timestamp, NsdError::InvalidPhaseWindow);
+    
+    // Check the window against every existing phase
+    // Windows never overlap, so a mint can only ever match one phase
+    let expected = ctx.accounts.config.phase_count as usize;
+    ctx.accounts.config.require_phase_window_free(
+        &ctx.accounts.config.key(),
+        &ctx.accounts.sale_phase.key(),
+        start_timestamp,
+        end_timestamp,
+        ctx.remaining_accounts,
+        expected,
+    )?;
+    
+    // Check the phase price
+    // The phase price must lie within the configured price band
+    ctx.accounts.config.require_price_in_band(price)?;
+    
+    // Set the sale phase values
+    // Link the phase back to its configuration
+    ctx.accounts.sale_phase.config = ctx.accounts.config.key();
+    // Store the phase identifier
+    ctx.accounts.sale_phase.phase_id = phase_id;
+    // Set the phase window
+    ctx.accounts.sale_phase.start_timestamp = start_timestamp;
+    ctx.accounts.sale_phase.end_timestamp = end_timestamp;
+    // Set the phase price
+    ctx.accounts.sale_phase.price = price;
+    // Set the phase limits
+    ctx.accounts.sale_phase.max_per_wallet = max_per_wallet;
+    ctx.accounts.sale_phase.allowlist_root = allowlist_root;
+    // Store the bump seed for the sale phase account
+    ctx.accounts.sale_phase.bump = ctx.bumps.sale_phase;
+    
+    // Count the new phase
+    // Mints now require an active phase
+    ctx.accounts.config.phase_count = ctx.accounts.config.phase_count
+        .checked_add(1)
+        .ok_or(NsdError::ArithmeticOverflow)?;
+    
+    emit!(SalePhaseSet {
+        config: ctx.accounts.config.key(),
+        phase_id: ctx.accounts.sale_phase.phase_id,
+        start_timestamp: ctx.accounts.sale_phase.start_timestamp,
+        end_timestamp: ctx.accounts.sale_phase.end_timestamp,
+        price: ctx.accounts.sale_phase.price,
+        max_per_wallet: ctx.accounts.sale_phase.max_per_wallet,
+        allowlist_root: ctx.accounts.sale_phase.allowlist_root,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for reading the current price
+/// 
+/// This struct defines the accounts needed for the current_price view instruction.
+/// Every account is read-only, so the instruction can be simulated without signatures.
+#[derive(Accounts)]
+pub struct CurrentPrice<'info> {
+    /// The configuration account that stores minting parameters
+    /// Seeded with "nsd_config" and the token mint
+    #[account(
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The sale phase a mint would be sent with, required once any phase is configured
+    /// Must belong to this configuration
+    #[account(
+        seeds = [
+            SALE_PHASE_SEED,
+            config.key().as_ref(),
+            sale_phase.phase_id.to_le_bytes().as_ref(),
+        ],
+        bump = sale_phase.bump,
+    )]
+    pub sale_phase: Option<Account<'info, SalePhase>>,
+}
+
+/// Read the current price
+/// 
+/// This view instruction returns the price in lamports of the next token at the current `Clock`
+/// time, with the same pricing rules as the mint instructions: the bonding curve, the Dutch auction,
+/// the active sale phase, the price tiers or the mint price. The price is returned as program return
+/// data, so clients read it by simulating the instruction.
+/// 
+/// # Accounts
+/// - `config` - The configuration account
+/// - `sale_phase` - Optional - The active sale phase, required once phases are configured
+/// 
+/// # Returns
+/// - `Result<u64>` - Price of the next token in lamports
+pub fn handler(
+    ctx: Context<CurrentPrice>,
+) -> Result<u64> {
+    // Resolve the phase that would apply to a mint right now
+    let timestamp = Clock::get()?.unix_timestamp;
+    let phase = ctx.accounts.config.active_phase(ctx.accounts.sale_phase.as_deref(), timestamp)?;
+    
+    ctx.accounts.config.unit_price(phase, timestamp)
+}
@@ -0,0 +1,196 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{spl_token_2022::instruction::AuthorityType, Mint, TokenInterface};
+
+/// Accounts required for finalizing a sale
+/// 
+/// This struct defines the accounts needed for the finalize_sale instruction.
+/// It includes the fee payer, configuration account, treasury, admin authority, token mint,
+/// mint authority PDA and, to sweep the treasury, a destination for the remaining proceeds.
+#[derive(Accounts)]
+pub struct FinalizeSale<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The treasury account holding the mint proceeds
+    /// Must be mutable and seeded with "treasury" and the config key
+    #[account(
+        mut,
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
+    /// The admin authority account that can finalize the sale
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The token mint account for NSD tokens
+    /// Must be mutable, match the mint stored in the configuration and be owned by the token program
+    #[account(
+        mut,
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The program PDA that holds the mint authority
+    /// Seeded with "mint_authority" and the config key, signs the revocation
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.mint_authority_bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The account receiving the remaining treasury balance
+    /// Only supplied to sweep the treasury
+    #[account(mut)]
+    /// CHECK: any account chosen by the admin may receive lamports
+    pub destination: Option<UncheckedAccount<'info>>,
+
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Implementation of helper methods for FinalizeSale
+impl<'info> FinalizeSale<'info> {
+    /// CPI to the token program to set the mint authority to `None`
+    /// 
+    /// The mint authority PDA signs the CPI with its seeds. Afterwards no one,
+    /// including this program, can mint the token again.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_revoke_mint_authority(&self) -> Result<()> {
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED,
+            config_key.as_ref(),
+            &[self.config.mint_authority_bump],
+        ]];
+        anchor_spl::token_interface::set_authority(
+            CpiContext::new_with_signer(self.token_program.to_account_info(),
+                anchor_spl::token_interface::SetAuthority {
+                    current_authority: self.mint_authority.to_account_info(),
+                    account_or_mint: self.token_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )
+    }
+}
+
+/// Finalize a sale
+/// 
+/// This instruction ends a sale for good. The token mint's mint authority is set to `None`,
+/// so holders can verify on the mint itself that the supply is fixed, and the configuration
+/// is marked finalized, after which every mint and `update_config` fail with `SaleFinalized`.
+/// When a destination is supplied, the treasury balance above its rent-exempt minimum and the
+/// bonding-curve reserve is swept to it; the reserve stays behind for holders selling back.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `treasury` - Writable - The treasury account holding the mint proceeds
+/// - `admin` - Signer - The admin authority account
+/// - `token_mint` - Writable - The configured NSD token mint
+/// - `mint_authority` - PDA - The program's mint authority, signs the revocation
+/// - `destination` - Writable, optional - The account receiving the swept treasury
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<FinalizeSale>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized already
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Check the program holds the mint authority
+    // Without it the revocation cannot be signed
+    require!(ctx.accounts.config.mint_authority_bound, NsdError::MintAuthorityNotBound);
+    
+    // Revoke the mint authority
+    // The supply is fixed at whatever has been minted so far
+    ctx.accounts.cpi_token_revoke_mint_authority()?;
+    
+    // Sweep the treasury if a destination was supplied
+    // The treasury keeps enough lamports to remain rent-exempt and to buy back the bonding-curve supply
+    let mut swept = 0;
+    if let Some(destination) = &ctx.accounts.destination {
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+        let reserve = ctx.accounts.config.curve_reserve()?;
+        swept = treasury_info.lamports()
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(reserve);
+        
+        ctx.accounts.treasury.sub_lamports(swept)?;
+        destination.add_lamports(swept)?;
+        
+        // Record the lamports withdrawn in the configuration
+        ctx.accounts.config.total_withdrawn = ctx.accounts.config.total_withdrawn
+            .checked_add(swept)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        
+        emit!(TreasuryWithdrawn {
+            config: ctx.accounts.config.key(),
+            destination: destination.key(),
+            amount: swept,
+            total_withdrawn: ctx.accounts.config.total_withdrawn,
+        });
+    }
+    
+    // Mark the sale finalized
+    // Minting is closed and the configuration becomes read-only
+    ctx.accounts.config.is_active = false;
+    ctx.accounts.config.is_finalized = true;
+    
+    emit!(SaleFinalized {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        total_minted: ctx.accounts.config.total_minted,
+        swept,
+    });
+    
+    Ok(())
+}
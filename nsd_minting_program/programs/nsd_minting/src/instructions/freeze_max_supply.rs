@@ -0,0 +1,79 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for freezing the maximum supply
+/// 
+/// This struct defines the accounts needed for the freeze_max_supply instruction.
+/// It includes the fee payer, configuration account and admin authority.
+#[derive(Accounts)]
+pub struct FreezeMaxSupply<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The admin authority account
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Freeze the maximum supply
+/// 
+/// This instruction permanently stops any further increase of the maximum supply.
+/// The supply can still be lowered down to the total minted. The flag is stored in the
+/// configuration account, so holders can verify the cap on-chain. There is no way to unfreeze.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `admin` - Signer - The admin authority account
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<FreezeMaxSupply>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Freeze the supply cap
+    // Freezing again is a no-op, the flag is never cleared
+    ctx.accounts.config.max_supply_frozen = true;
+    
+    // Check the configuration
+    ctx.accounts.config.validate()?;
+    
+    emit!(MaxSupplyFrozen {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        max_supply: ctx.accounts.config.max_supply,
+    });
+    
+    Ok(())
+}
@@ -10,12 +10,6 @@
 // Import required modules and macros
 use crate::*;
 use anchor_lang::prelude::*;
-use std::str::FromStr;
-
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
-};
 
 /// Accounts required for initializing the NSD minting configuration
 /// 
@@ -26,6 +20,8 @@ use anchor_spl::{
     token_mint: Pubkey,
     max_supply: u64,
     mint_price: u64,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
 )]
 pub struct InitializeConfig<'info> {
     /// The fee payer for account initialization
@@ -37,15 +33,30 @@ pub struct InitializeConfig<'info> {
     /// This account is initialized with specific space and seeds
     #[account(
         init,
-        space=98,
+        space=585,
         payer=fee_payer,
         seeds = [
-            b"nsd_config",
+            CONFIG_SEED,
+            token_mint.as_ref(),
         ],
         bump,
     )]
     pub config: Account<'info, NsdConfig>,
 
+    /// The treasury account that collects mint proceeds
+    /// This account is initialized with specific space and seeds
+    #[account(
+        init,
+        space=41,
+        payer=fee_payer,
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
     /// The admin authority account that can modify configuration
     /// Must be a signer account
     pub admin: Signer<'info>,
@@ -62,6 +73,7 @@ pub struct InitializeConfig<'info> {
 /// # Accounts
 /// - `fee_payer` - Writable, signer - The account paying for account initialization
 /// - `config` - Writable - The configuration account to be initialized
+/// - `treasury` - Writable - The treasury account to be initialized
 /// - `admin` - Signer - The admin authority account
 /// - `system_program` - System program - Required for account initialization
 /// 
@@ -69,6 +81,8 @@ pub struct InitializeConfig<'info> {
 /// - `token_mint` - The mint address for NSD token
 /// - `max_supply` - Maximum supply of NSD tokens
 /// - `mint_price` - Price per NSD token in lamports
+/// - `min_price` - Lowest price the admin may set, if bounded
+/// - `max_price` - Highest price the admin may set, if bounded
 /// 
 /// # Returns
 /// - `Result<()>` - Success or error
@@ -77,22 +91,43 @@ pub fn handler(
     token_mint: Pubkey,
     max_supply: u64,
     mint_price: u64,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
 ) -> Result<()> {
     // Set the configuration values
-    // Store the admin authority
-    ctx.accounts.config.admin = ctx.accounts.admin.key();
-    // Store the token mint address
-    ctx.accounts.config.token_mint = token_mint;
-    // Set the maximum supply
-    ctx.accounts.config.max_supply = max_supply;
-    // Set the mint price per token
-    ctx.accounts.config.mint_price = mint_price;
-    // Initialize total minted to zero
-    ctx.accounts.config.total_minted = 0;
-    // Set minting to active by default
-    ctx.accounts.config.is_active = true;
-    // Store the bump seed for the config account
+    ctx.accounts.config.init(
+        ctx.accounts.admin.key(),
+        token_mint,
+        max_supply,
+        mint_price,
+        min_price,
+        max_price,
+    )?;
+    // Store the bump seeds for the config and treasury accounts
     ctx.accounts.config.bump = ctx.bumps.config;
+    ctx.accounts.config.treasury_bump = ctx.bumps.treasury;
+    // The mint authority is bound later through bind_mint_authority
+    // Record the PDA bump up front so mints before binding fail with MintAuthorityNotBound
+    let config_key = ctx.accounts.config.key();
+    ctx.accounts.config.mint_authority_bump = Pubkey::find_program_address(
+        &[MINT_AUTHORITY_SEED, config_key.as_ref()],
+        ctx.program_id,
+    ).1;
+    // Decimals are recorded from the mint when the mint authority is bound,
+    // and externally created mints do not use the freeze authority PDA
+
+    // Link the treasury back to its configuration
+    ctx.accounts.treasury.config = ctx.accounts.config.key();
+    // Store the bump seed for the treasury account
+    ctx.accounts.treasury.bump = ctx.bumps.treasury;
+
+    emit!(ConfigInitialized {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.config.admin,
+        token_mint: ctx.accounts.config.token_mint,
+        max_supply: ctx.accounts.config.max_supply,
+        mint_price: ctx.accounts.config.mint_price,
+    });
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,264 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+
+use anchor_spl::token_interface::TokenInterface;
+
+/// Accounts required for initializing a sale together with its token mint
+/// 
+/// This struct defines the accounts needed for the initialize_sale instruction.
+/// It includes the fee payer, configuration and treasury accounts, the new token mint,
+/// the authority PDAs and the programs used to create the mint.
+#[derive(Accounts)]
+#[instruction(
+    decimals: u8,
+    max_supply: u64,
+    mint_price: u64,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+)]
+pub struct InitializeSale<'info> {
+    /// The fee payer for account initialization
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// This account is initialized with specific space and seeds
+    #[account(
+        init,
+        space=585,
+        payer=fee_payer,
+        seeds = [
+            CONFIG_SEED,
+            token_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The treasury account that collects mint proceeds
+    /// This account is initialized with specific space and seeds
+    #[account(
+        init,
+        space=41,
+        payer=fee_payer,
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
+    /// The admin authority account that can modify configuration
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The new token mint account for NSD tokens
+    /// Must be a fresh keypair signing for its own creation
+    #[account(mut)]
+    pub token_mint: Signer<'info>,
+
+    /// The program PDA that becomes the mint authority
+    /// Seeded with "mint_authority" and the config key
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The program PDA that becomes the freeze authority
+    /// Seeded with "freeze_authority" and the config key
+    #[account(
+        seeds = [
+            FREEZE_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    /// The token program that will own the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program account for account initialization
+    pub system_program: Program<'info, System>,
+
+    /// The rent sysvar, required by the initialize_mint instruction
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Implementation of helper methods for InitializeSale
+impl<'info> InitializeSale<'info> {
+    /// Whether the mint is created under the Token-2022 program
+    pub fn is_token_2022(&self) -> bool {
+        self.token_program.key() == anchor_spl::token_2022::ID
+    }
+
+    /// CPI to the System program to allocate the token mint account
+    /// 
+    /// This method creates the mint account, funded for rent-exemption and owned by the token program.
+    /// Token-2022 mints are sized for the metadata-pointer extension.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_system_create_mint_account(&self) -> Result<()> {
+        let space = if self.is_token_2022() {
+            token_extensions::mint_space_with_metadata_pointer()?
+        } else {
+            anchor_spl::token::spl_token::state::Mint::LEN
+        };
+        anchor_lang::system_program::create_account(
+            CpiContext::new(self.system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: self.fee_payer.to_account_info(),
+                    to: self.token_mint.to_account_info(),
+                }
+            ),
+            self.rent.minimum_balance(space),
+            space as u64,
+            &self.token_program.key(),
+        )
+    }
+
+    /// CPI to the Token-2022 program to point the mint's metadata at the mint itself
+    /// 
+    /// This lets set_token_metadata store the metadata in the mint's token-metadata extension.
+    /// The mint authority PDA may later move the pointer.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_initialize_metadata_pointer(&self) -> Result<()> {
+        anchor_spl::token_2022_extensions::metadata_pointer_initialize(
+            CpiContext::new(self.token_program.to_account_info(),
+                anchor_spl::token_2022_extensions::MetadataPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                }
+            ),
+            Some(self.mint_authority.key()),
+            Some(self.token_mint.key()),
+        )
+    }
+
+    /// CPI to the token program to initialize the token mint
+    /// 
+    /// This method sets the mint and freeze authorities to the program PDAs.
+    /// 
+    /// # Parameters
+    /// - `decimals` - Number of decimals for the token mint
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_initialize_mint(&self, decimals: u8) -> Result<()> {
+        anchor_spl::token_interface::initialize_mint(
+            CpiContext::new(self.token_program.to_account_info(),
+                anchor_spl::token_interface::InitializeMint {
+                    mint: self.token_mint.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                }
+            ),
+            decimals,
+            &self.mint_authority.key(),
+            Some(&self.freeze_authority.key()),
+        )
+    }
+}
+
+/// Initialize a sale and create its NSD token mint
+/// 
+/// This instruction creates the token mint from inside the program, with the mint and
+/// freeze authorities set to program PDAs, and initializes the configuration for it.
+/// Passing the Token-2022 program creates a Token-2022 mint whose metadata pointer refers
+/// to the mint itself, so its metadata can live in the mint without a separate metadata program.
+/// There is no window in which a mismatched or externally controlled mint can be configured.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for account initialization
+/// - `config` - Writable - The configuration account to be initialized
+/// - `treasury` - Writable - The treasury account to be initialized
+/// - `admin` - Signer - The admin authority account
+/// - `token_mint` - Writable, signer - The new token mint account
+/// - `mint_authority` - PDA - The program's mint authority
+/// - `freeze_authority` - PDA - The program's freeze authority
+/// - `token_program` - Token program - SPL Token or Token-2022, owner of the new mint
+/// - `system_program` - System program - Required for account initialization
+/// - `rent` - Rent sysvar - Required for mint initialization
+/// 
+/// # Parameters
+/// - `decimals` - Number of decimals for the NSD token mint
+/// - `max_supply` - Maximum supply of NSD tokens
+/// - `mint_price` - Price per NSD token in lamports
+/// - `min_price` - Lowest price the admin may set, if bounded
+/// - `max_price` - Highest price the admin may set, if bounded
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<InitializeSale>,
+    decimals: u8,
+    max_supply: u64,
+    mint_price: u64,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+) -> Result<()> {
+    // Create the token mint
+    // Allocate the mint account and initialize it with the program PDAs as authorities
+    ctx.accounts.cpi_system_create_mint_account()?;
+    // Extensions must be initialized before the mint itself
+    if ctx.accounts.is_token_2022() {
+        ctx.accounts.cpi_token_initialize_metadata_pointer()?;
+    }
+    ctx.accounts.cpi_token_initialize_mint(decimals)?;
+
+    // Set the configuration values
+    ctx.accounts.config.init(
+        ctx.accounts.admin.key(),
+        ctx.accounts.token_mint.key(),
+        max_supply,
+        mint_price,
+        min_price,
+        max_price,
+    )?;
+    // Store the bump seeds for the config and treasury accounts
+    ctx.accounts.config.bump = ctx.bumps.config;
+    ctx.accounts.config.treasury_bump = ctx.bumps.treasury;
+    // The mint was created with the program PDA as its authority
+    ctx.accounts.config.mint_authority_bound = true;
+    ctx.accounts.config.mint_authority_bump = ctx.bumps.mint_authority;
+    // Record the mint decimals
+    ctx.accounts.config.decimals = decimals;
+    // Store the bump seed for the freeze authority PDA
+    ctx.accounts.config.freeze_authority_bump = ctx.bumps.freeze_authority;
+
+    // Link the treasury back to its configuration
+    ctx.accounts.treasury.config = ctx.accounts.config.key();
+    // Store the bump seed for the treasury account
+    ctx.accounts.treasury.bump = ctx.bumps.treasury;
+
+    emit!(ConfigInitialized {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.config.admin,
+        token_mint: ctx.accounts.config.token_mint,
+        max_supply: ctx.accounts.config.max_supply,
+        mint_price: ctx.accounts.config.mint_price,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+
+use anchor_spl::{
+    metadata::Metadata,
+    token_interface::{Mint, TokenInterface},
+};
+
+/// Accounts required for locking token metadata
+/// 
+/// This struct defines the accounts needed for the lock_metadata instruction.
+/// It includes the fee payer, configuration account, admin authority, and the accounts
+/// needed to lock the token's public metadata: the metadata account and the Metaplex
+/// accounts for SPL Token mints, or the mint's own token-metadata extension for Token-2022 mints.
+#[derive(Accounts)]
+pub struct LockMetadata<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Seeded with "nsd_config" and the token mint
+    #[account(
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The metadata account to lock, only required for SPL Token mints
+    /// Must be mutable and seeded with "metadata" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            METADATA_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Option<Account<'info, NsdTokenMetadata>>,
+
+    /// The admin authority account that can lock metadata
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The program PDA that holds the mint authority
+    /// Signs the Metaplex CPI as update authority
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.mint_authority_bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The token mint account for NSD tokens
+    /// Must match the configured mint and be owned by the token program
+    /// Writable so Token-2022 mints can update the metadata extension
+    #[account(
+        mut,
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The Metaplex metadata account for the token mint, only required for SPL Token mints
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            anchor_spl::metadata::ID.as_ref(),
+            config.token_mint.as_ref(),
+        ],
+        seeds::program = anchor_spl::metadata::ID,
+        bump,
+    )]
+    /// CHECK: validated by the Token Metadata program
+    pub metaplex_metadata: Option<UncheckedAccount<'info>>,
+
+    /// The Metaplex Token Metadata program, only required for SPL Token mints
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Implementation of helper methods for LockMetadata
+impl<'info> LockMetadata<'info> {
+    /// CPI to the Token-2022 program to remove the update authority of the mint's token-metadata extension
+    /// 
+    /// Without an update authority the extension can never be changed again.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_metadata_remove_update_authority(&self) -> Result<()> {
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED,
+            config_key.as_ref(),
+            &[self.config.mint_authority_bump],
+        ]];
+        anchor_spl::token_2022_extensions::token_metadata_update_authority(
+            CpiContext::new_with_signer(self.token_program.to_account_info(),
+                anchor_spl::token_2022_extensions::TokenMetadataUpdateAuthority {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: self.mint.to_account_info(),
+                    current_authority: self.mint_authority.to_account_info(),
+                    new_authority: self.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            OptionalNonZeroPubkey::default(),
+        )
+    }
+}
+
+/// Lock metadata for NSD tokens
+/// 
+/// This instruction makes the token metadata permanently immutable, including the Token-2022
+/// metadata extension or the Metaplex metadata account. It cannot be undone.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - The configuration account
+/// - `metadata` - Optional, writable - The metadata account to lock for SPL Token mints
+/// - `admin` - Signer - The admin authority account
+/// - `mint_authority` - PDA - The program's mint authority, signs the metadata CPI
+/// - `mint` - Writable - The NSD token mint account
+/// - `metaplex_metadata` - Optional, writable - The Metaplex metadata account to lock for SPL Token mints
+/// - `token_metadata_program` - Optional - The Metaplex Token Metadata program for SPL Token mints
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<LockMetadata>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Lock the public metadata
+    // Neither Token-2022 nor the Token Metadata program lets it become mutable again
+    if token_extensions::is_token_2022(&ctx.accounts.mint.to_account_info()) {
+        // Token-2022 mints hold the metadata natively, without the metadata account
+        require!(ctx.accounts.metadata.is_none(), NsdError::UnexpectedMetadataAccount);
+        
+        // Check the metadata is not already locked
+        // Locking removes the extension's update authority
+        let current = token_extensions::read_token_metadata(&ctx.accounts.mint.to_account_info())?;
+        require!(Option::<Pubkey>::from(current.update_authority).is_some(), NsdError::MetadataLocked);
+        
+        ctx.accounts.cpi_token_metadata_remove_update_authority()?;
+    } else {
+        let Some(metadata) = ctx.accounts.metadata.as_mut() else {
+            return err!(NsdError::MissingMetadataAccounts);
+        };
+        
+        // Check the metadata is not already locked
+        require!(!metadata.is_locked, NsdError::MetadataLocked);
+        
+        // Lock the metadata
+        metadata.is_locked = true;
+        
+        metaplex::update_metadata(
+            &ctx.accounts.config,
+            &ctx.accounts.mint_authority.to_account_info(),
+            ctx.accounts.metadata.as_ref(),
+            ctx.accounts.metaplex_metadata.as_ref(),
+            ctx.accounts.token_metadata_program.as_ref(),
+            false,
+        )?;
+    }
+    
+    emit!(MetadataLocked {
+        config: ctx.accounts.config.key(),
+        mint: ctx.accounts.config.token_mint,
+    });
+    
+    Ok(())
+}
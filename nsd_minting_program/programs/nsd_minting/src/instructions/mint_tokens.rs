@@ -10,17 +10,17 @@
 // Import required modules and macros
 use crate::*;
 use anchor_lang::prelude::*;
-use std::str::FromStr;
 
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 /// Accounts required for minting NSD tokens
 /// 
 /// This struct defines the accounts needed for the mint_tokens instruction.
 /// It includes the fee payer, configuration account, user accounts, and token program accounts.
+/// This is the legacy layout; new clients should use MintTokensV2.
 #[derive(Accounts)]
 #[instruction(
     mint_amount: u64,
@@ -32,22 +32,35 @@ pub struct MintTokens<'info> {
     pub fee_payer: Signer<'info>,
 
     /// The configuration account that stores minting parameters
-    /// Must be mutable and seeded with "nsd_config"
+    /// Must be mutable and seeded with "nsd_config" and the token mint
     #[account(
         mut,
         seeds = [
-            b"nsd_config",
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
         ],
-        bump,
+        bump = config.bump,
     )]
     pub config: Account<'info, NsdConfig>,
 
-    /// User's token account (unchecked due to dynamic nature)
-    /// This account will hold the minted tokens
+    /// The treasury account that receives the mint price
+    /// Must be mutable and seeded with "treasury" and the config key
+    #[account(
+        mut,
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
+    /// Unused, the tokens are minted to `assoc_token_account`
+    /// Kept so the legacy account indices stay stable for existing clients
     #[account(
         mut,
     )]
-    /// CHECK: implement manual checks if needed
+    /// CHECK: ignored by the instruction
     pub user_token_account: UncheckedAccount<'info>,
 
     /// The user who is minting tokens
@@ -61,7 +74,8 @@ pub struct MintTokens<'info> {
         space=57,
         payer=fee_payer,
         seeds = [
-            b"nsd_user",
+            USER_SEED,
+            config.key().as_ref(),
             user.key().as_ref(),
         ],
         bump,
@@ -69,41 +83,86 @@ pub struct MintTokens<'info> {
     pub user_account: Account<'info, NsdUser>,
 
     /// The token mint account for NSD tokens
-    /// This is the SPL token mint account
-    pub token_mint: Account<'info, Mint>,
+    /// Must match the mint stored in the configuration
+    #[account(
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// The system program account for account initialization
     pub system_program: Program<'info, System>,
 
     /// The mint account for the token being minted
-    /// Must be mutable
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    /// Must be mutable, match the mint stored in the configuration and be owned by the token program
+    #[account(
+        mut,
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// The associated token account for the user
     /// This is where the minted tokens will be sent
-    pub assoc_token_account: Account<'info, TokenAccount>,
+    /// Created and paid for by the fee payer if it doesn't exist yet
+    /// Must hold the configured mint and belong to the user
+    #[account(
+        init_if_needed,
+        payer=fee_payer,
+        associated_token::mint = mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program,
+        constraint = assoc_token_account.mint == config.token_mint @ NsdError::InvalidTokenMint,
+        constraint = assoc_token_account.owner == user.key() @ NsdError::InvalidRecipient,
+    )]
+    pub assoc_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// The owner of the mint account (authority)
-    /// Must be a signer account
-    pub owner: Signer<'info>,
+    /// The program PDA that holds the mint authority
+    /// Seeded with "mint_authority" and the config key
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.mint_authority_bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
 
     /// The wallet account for the new associated token account
-    /// CHECK: implement manual checks if needed
     /// This is used for creating associated token accounts
-    /// CHECK: implement manual checks if needed
+    /// Must be the user's wallet
+    #[account(
+        address = user.key() @ NsdError::InvalidRecipient,
+    )]
+    /// CHECK: checked against the user's key
     pub wallet: UncheckedAccount<'info>,
 
-    /// The SPL Token program account
-    pub token_program: Program<'info, Token>,
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Associated Token program, used to create the user's token account
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The sale phase being minted in, required once any phase is configured
+    /// Must belong to this configuration
+    #[account(
+        seeds = [
+            SALE_PHASE_SEED,
+            config.key().as_ref(),
+            sale_phase.phase_id.to_le_bytes().as_ref(),
+        ],
+        bump = sale_phase.bump,
+    )]
+    pub sale_phase: Option<Account<'info, SalePhase>>,
 }
 
 /// Implementation of helper methods for MintTokens
 impl<'info> MintTokens<'info> {
     /// CPI (Cross-Program Invocation) to mint tokens to an account
     /// 
-    /// This method performs a cross-program invocation to the SPL Token program
+    /// This method performs a cross-program invocation to the token program
     /// to mint tokens to the specified associated token account.
+    /// The mint authority PDA signs the CPI with its seeds.
     /// 
     /// # Parameters
     /// - `amount` - The amount of tokens to mint
@@ -111,84 +170,125 @@ impl<'info> MintTokens<'info> {
     /// # Returns
     /// - `Result<()>` - Success or error
     pub fn cpi_token_mint_to(&self, amount: u64) -> Result<()> {
-        anchor_spl::token::mint_to(
-            CpiContext::new(self.token_program.to_account_info(), 
-                anchor_spl::token::MintTo {
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED,
+            config_key.as_ref(),
+            &[self.config.mint_authority_bump],
+        ]];
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(self.token_program.to_account_info(), 
+                anchor_spl::token_interface::MintTo {
                     mint: self.mint.to_account_info(),
                     to: self.assoc_token_account.to_account_info(),
-                    authority: self.owner.to_account_info()
-                }
+                    authority: self.mint_authority.to_account_info()
+                },
+                signer_seeds,
             ),
             amount, 
         )
     }
+
+    /// CPI to the System program to pay the mint price into the treasury
+    /// 
+    /// This method transfers lamports from the fee payer to the treasury account.
+    /// 
+    /// # Parameters
+    /// - `amount` - The amount of lamports to transfer
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_system_transfer_to_treasury(&self, amount: u64) -> Result<()> {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(self.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: self.fee_payer.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                }
+            ),
+            amount,
+        )
+    }
 }
 
 /// Mint NSD tokens for a user
 /// 
-/// This instruction mints NSD tokens to a user's associated token account.
+/// This instruction mints NSD tokens to a user's associated token account,
+/// creating the token account first if it doesn't exist yet.
 /// It performs validation checks before minting and updates the configuration.
 /// 
 /// # Accounts
 /// - `fee_payer` - Writable, signer - The account paying for transaction fees
 /// - `config` - Writable - The configuration account
-/// - `user_token_account` - Writable - User's token account
+/// - `treasury` - Writable - The treasury account receiving the mint price
+/// - `user_token_account` - Writable - Ignored, kept for the legacy account order
 /// - `user` - Signer - The user who is minting tokens
 /// - `user_account` - Writable - User's minting history account
 /// - `token_mint` - Mint - The NSD token mint account
 /// - `system_program` - System program - Required for account initialization
 /// - `mint` - Mint - The mint account for the token being minted
-/// - `assoc_token_account` - Token account - The associated token account for the user
-/// - `owner` - Signer - The mint's minting authority
+/// - `assoc_token_account` - Token account - The associated token account for the user, created if needed
+/// - `mint_authority` - PDA - The program's mint authority, signs the mint CPI
 /// - `wallet` - Wallet address - Wallet address for the new associated token account
-/// - `token_program` - SPL Token program - The SPL Token program account
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
+/// - `associated_token_program` - Associated Token program - Creates the user's token account
+/// - `sale_phase` - Optional - The active sale phase, required once phases are configured
 /// 
 /// # Parameters
 /// - `mint_amount` - Number of tokens to mint
+/// - `max_total_cost` - Most lamports the buyer accepts paying for the mint
 /// 
 /// # Returns
 /// - `Result<()>` - Success or error
 pub fn handler(
     ctx: Context<MintTokens>,
     mint_amount: u64,
+    max_total_cost: u64,
 ) -> Result<()> {
-    // Check if minting is active
-    // If not active, throw an error
-    require!(ctx.accounts.config.is_active, NsdError::MintingNotActive);
+    // Validate the mint against the sale rules
+    // Checks the active flag, sale phase, mint authority, max supply and per-wallet limits, and calculates the total cost
+    let timestamp = Clock::get()?.unix_timestamp;
+    let total_cost = ctx.accounts.config.validate_mint(
+        &ctx.accounts.user_account,
+        ctx.accounts.sale_phase.as_deref(),
+        mint_amount,
+        timestamp,
+    )?;
     
-    // Check if we haven't exceeded max supply
-    // If minting would exceed the maximum supply, throw an error
-    require!(
-        ctx.accounts.config.total_minted + mint_amount <= ctx.accounts.config.max_supply,
-        NsdError::ExceedsMaxSupply
-    );
+    // Check the price has not moved past the buyer's limit
+    // The price can change between quoting and landing, e.g. through update_config
+    require!(total_cost <= max_total_cost, NsdError::PriceExceedsLimit);
     
     // Check if user has sufficient balance to pay for minting
-    // Calculate the total cost of minting
-    let total_cost = mint_amount * ctx.accounts.config.mint_price;
     // If the fee payer doesn't have enough lamports, throw an error
     require!(
         ctx.accounts.fee_payer.lamports() >= total_cost,
         NsdError::InsufficientFunds
     );
     
+    // Collect payment
+    // Transfer the total cost from the fee payer into the treasury
+    ctx.accounts.cpi_system_transfer_to_treasury(total_cost)?;
+    
     // Mint tokens to user
     // Perform the CPI to mint tokens using the helper method
     ctx.accounts.cpi_token_mint_to(mint_amount)?;
     
-    // Update config with new minted amount
-    // Increment the total minted count in the configuration
-    ctx.accounts.config.total_minted += mint_amount;
+    // Update config with new minted amount and revenue
+    ctx.accounts.config.record_mint(mint_amount, total_cost)?;
     
     // Update user account
-    // Set the user's public key
-    ctx.accounts.user_account.user = ctx.accounts.user.key();
-    // Increment the user's minted token count
-    ctx.accounts.user_account.tokens_minted += mint_amount;
-    // Set the timestamp of the last mint
-    ctx.accounts.user_account.last_mint_timestamp = Clock::get()?.unix_timestamp;
-    // Store the bump seed for the user account
-    ctx.accounts.user_account.bump = ctx.bumps.user_account;
+    // Record the mint in the user's history
+    let user = ctx.accounts.user.key();
+    ctx.accounts.user_account.record_mint(user, mint_amount, timestamp, ctx.bumps.user_account)?;
+    
+    emit!(TokensMinted {
+        config: ctx.accounts.config.key(),
+        user,
+        amount: mint_amount,
+        price_paid: total_cost,
+        total_minted: ctx.accounts.config.total_minted,
+    });
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,283 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{Mint, TokenInterface},
+};
+
+/// Accounts required for minting NSD tokens with the validated layout
+/// 
+/// This struct defines the accounts needed for the mint_tokens_v2 instruction.
+/// Every account is tied to the configuration or to the minting user, and the
+/// redundant accounts of the legacy layout are removed.
+#[derive(Accounts)]
+#[instruction(
+    mint_amount: u64,
+)]
+pub struct MintTokensV2<'info> {
+    /// The fee payer for account initialization and the mint price
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The treasury account that receives the mint price
+    /// Must be mutable and seeded with "treasury" and the config key
+    #[account(
+        mut,
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
+    /// The token mint account for NSD tokens
+    /// Must be mutable, match the mint stored in the configuration and be owned by the token program
+    #[account(
+        mut,
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The user who is minting tokens
+    /// Must be a signer account
+    pub user: Signer<'info>,
+
+    /// The user account that tracks minting history
+    /// Initialized if needed with specific space and seeds
+    #[account(
+        init_if_needed,
+        space=57,
+        payer=fee_payer,
+        seeds = [
+            USER_SEED,
+            config.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_account: Account<'info, NsdUser>,
+
+    /// The user's associated token account for the NSD mint
+    /// This is where the minted tokens will be sent
+    /// Must be the user's associated token address, checked before the handler creates it if needed
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&user.key(), &token_mint.key(), &token_program.key()) @ NsdError::InvalidRecipient,
+    )]
+    /// CHECK: checked against the user's associated token address, created by the Associated Token program
+    pub user_token_account: UncheckedAccount<'info>,
+
+    /// The program PDA that holds the mint authority
+    /// Seeded with "mint_authority" and the config key
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.mint_authority_bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Associated Token program, used to create the user's token account
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program account for account initialization
+    pub system_program: Program<'info, System>,
+
+    /// The sale phase being minted in, required once any phase is configured
+    /// Must belong to this configuration
+    #[account(
+        seeds = [
+            SALE_PHASE_SEED,
+            config.key().as_ref(),
+            sale_phase.phase_id.to_le_bytes().as_ref(),
+        ],
+        bump = sale_phase.bump,
+    )]
+    pub sale_phase: Option<Account<'info, SalePhase>>,
+}
+
+/// Implementation of helper methods for MintTokensV2
+impl<'info> MintTokensV2<'info> {
+    /// CPI to the Associated Token program to create the user's token account if it doesn't exist yet
+    /// 
+    /// The fee payer pays the rent. An existing account is left as is.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_create_user_token_account(&self) -> Result<()> {
+        anchor_spl::associated_token::create_idempotent(
+            CpiContext::new(self.associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: self.fee_payer.to_account_info(),
+                    associated_token: self.user_token_account.to_account_info(),
+                    authority: self.user.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+            ),
+        )
+    }
+
+    /// CPI (Cross-Program Invocation) to mint tokens to the user's token account
+    /// 
+    /// The mint authority PDA signs the CPI with its seeds.
+    /// 
+    /// # Parameters
+    /// - `amount` - The amount of tokens to mint
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_mint_to(&self, amount: u64) -> Result<()> {
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED,
+            config_key.as_ref(),
+            &[self.config.mint_authority_bump],
+        ]];
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(self.token_program.to_account_info(),
+                anchor_spl::token_interface::MintTo {
+                    mint: self.token_mint.to_account_info(),
+                    to: self.user_token_account.to_account_info(),
+                    authority: self.mint_authority.to_account_info()
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    /// CPI to the System program to pay the mint price into the treasury
+    /// 
+    /// # Parameters
+    /// - `amount` - The amount of lamports to transfer
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_system_transfer_to_treasury(&self, amount: u64) -> Result<()> {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(self.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: self.fee_payer.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                }
+            ),
+            amount,
+        )
+    }
+}
+
+/// Mint NSD tokens for a user with the validated account layout
+/// 
+/// This instruction mints NSD tokens to the user's associated token account for the configured mint,
+/// creating the token account first if needed so a brand-new wallet can mint in one transaction.
+/// A mint that differs from the configuration or a token account that doesn't belong to the user is rejected.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying the mint price and transaction fees
+/// - `config` - Writable - The configuration account
+/// - `treasury` - Writable - The treasury account receiving the mint price
+/// - `token_mint` - Writable - The configured NSD token mint
+/// - `user` - Signer - The user who is minting tokens
+/// - `user_account` - Writable - User's minting history account
+/// - `user_token_account` - Writable - The user's associated token account for the mint, created if needed
+/// - `mint_authority` - PDA - The program's mint authority, signs the mint CPI
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
+/// - `associated_token_program` - Associated Token program - Creates the user's token account
+/// - `system_program` - System program - Required for account initialization
+/// - `sale_phase` - Optional - The active sale phase, required once phases are configured
+/// 
+/// # Parameters
+/// - `mint_amount` - Number of tokens to mint
+/// - `max_total_cost` - Most lamports the buyer accepts paying for the mint
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<MintTokensV2>,
+    mint_amount: u64,
+    max_total_cost: u64,
+) -> Result<()> {
+    // Validate the mint against the sale rules
+    // Checks the active flag, sale phase, mint authority, max supply and per-wallet limits, and calculates the total cost
+    let timestamp = Clock::get()?.unix_timestamp;
+    let total_cost = ctx.accounts.config.validate_mint(
+        &ctx.accounts.user_account,
+        ctx.accounts.sale_phase.as_deref(),
+        mint_amount,
+        timestamp,
+    )?;
+    
+    // Check the price has not moved past the buyer's limit
+    // The price can change between quoting and landing, e.g. through update_config
+    require!(total_cost <= max_total_cost, NsdError::PriceExceedsLimit);
+    
+    // Check if user has sufficient balance to pay for minting
+    // If the fee payer doesn't have enough lamports, throw an error
+    require!(
+        ctx.accounts.fee_payer.lamports() >= total_cost,
+        NsdError::InsufficientFunds
+    );
+    
+    // Collect payment
+    // Transfer the total cost from the fee payer into the treasury
+    ctx.accounts.cpi_system_transfer_to_treasury(total_cost)?;
+    
+    // Create the user's token account if needed
+    // Its address was checked against the user's associated token address
+    ctx.accounts.cpi_create_user_token_account()?;
+    
+    // Mint tokens to user
+    // Perform the CPI to mint tokens using the helper method
+    ctx.accounts.cpi_token_mint_to(mint_amount)?;
+    
+    // Update config with new minted amount and revenue
+    ctx.accounts.config.record_mint(mint_amount, total_cost)?;
+    
+    // Update user account
+    // Record the mint in the user's history
+    let user = ctx.accounts.user.key();
+    ctx.accounts.user_account.record_mint(user, mint_amount, timestamp, ctx.bumps.user_account)?;
+    
+    emit!(TokensMinted {
+        config: ctx.accounts.config.key(),
+        user,
+        amount: mint_amount,
+        price_paid: total_cost,
+        total_minted: ctx.accounts.config.total_minted,
+    });
+    
+    Ok(())
+}
@@ -3,8 +3,54 @@ pub mod initialize_config;
 pub mod mint_tokens;
 pub mod update_config;
 pub mod set_token_metadata;
+pub mod withdraw_treasury;
+pub mod bind_mint_authority;
+pub mod initialize_sale;
+pub mod mint_tokens_v2;
+pub mod presale_mint;
+pub mod create_sale_phase;
+pub mod update_sale_phase;
+pub mod close_sale_phase;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod update_token_metadata;
+pub mod lock_metadata;
+pub mod freeze_max_supply;
+pub mod finalize_sale;
+pub mod set_price_tiers;
+pub mod set_bonding_curve;
+pub mod sell_tokens;
+pub mod set_dutch_auction;
+pub mod current_price;
+pub mod set_payment_mints;
+pub mod mint_tokens_with_token;
+pub mod withdraw_payment_tokens;
+pub mod quote_mint;
 
 pub use initialize_config::*;
 pub use mint_tokens::*;
 pub use update_config::*;
 pub use set_token_metadata::*;
+pub use withdraw_treasury::*;
+pub use bind_mint_authority::*;
+pub use initialize_sale::*;
+pub use mint_tokens_v2::*;
+pub use create_sale_phase::*;
+pub use update_sale_phase::*;
+pub use close_sale_phase::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use cancel_admin_transfer::*;
+pub use update_token_metadata::*;
+pub use lock_metadata::*;
+pub use freeze_max_supply::*;
+pub use finalize_sale::*;
+pub use set_price_tiers::*;
+pub use set_bonding_curve::*;
+pub use sell_tokens::*;
+pub use set_dutch_auction::*;
+pub use current_price::*;
+pub use set_payment_mints::*;
+pub use mint_tokens_with_token::*;
+pub use withdraw_payment_tokens::*;
@@ -0,0 +1,120 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Mint NSD tokens during the allowlist presale
+/// 
+/// This instruction lets an allowlisted wallet mint up to its allocation while the presale is open.
+/// The wallet proves its `(user, allocation)` leaf against the Merkle root of the active sale phase,
+/// or the one stored in the configuration.
+/// It uses the same validated account layout as mint_tokens_v2.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying the mint price and transaction fees
+/// - `config` - Writable - The configuration account
+/// - `treasury` - Writable - The treasury account receiving the mint price
+/// - `token_mint` - Writable - The configured NSD token mint
+/// - `user` - Signer - The allowlisted user who is minting tokens
+/// - `user_account` - Writable - User's minting history account
+/// - `user_token_account` - Writable - The user's associated token account for the mint, created if needed
+/// - `mint_authority` - PDA - The program's mint authority, signs the mint CPI
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
+/// - `associated_token_program` - Associated Token program - Creates the user's token account
+/// - `system_program` - System program - Required for account initialization
+/// - `sale_phase` - Optional - The active sale phase, required once phases are configured
+/// 
+/// # Parameters
+/// - `mint_amount` - Number of tokens to mint
+/// - `max_total_cost` - Most lamports the buyer accepts paying for the mint
+/// - `allocation` - The user's presale allocation from the allowlist leaf
+/// - `proof` - Merkle proof for the user's leaf
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<MintTokensV2>,
+    mint_amount: u64,
+    max_total_cost: u64,
+    allocation: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    // Check the sale phase window
+    // An active phase's allowlist root takes precedence over the configured root
+    let timestamp = Clock::get()?.unix_timestamp;
+    let phase = ctx.accounts.config.active_phase(ctx.accounts.sale_phase.as_deref(), timestamp)?;
+    
+    // Check the presale is open
+    // The presale is open while an allowlist root is configured
+    let root = phase
+        .and_then(|p| p.allowlist_root)
+        .or(ctx.accounts.config.allowlist_root)
+        .ok_or(NsdError::PresaleNotActive)?;
+    
+    // Verify the user is on the allowlist
+    // The leaf commits to both the user and their allocation
+    let leaf = merkle::allowlist_leaf(&ctx.accounts.user.key(), allocation);
+    require!(merkle::verify_proof(&root, leaf, &proof), NsdError::InvalidMerkleProof);
+    
+    // Check the user stays within their allocation
+    let allocation_used = ctx.accounts.user_account.tokens_minted
+        .checked_add(mint_amount)
+        .ok_or(NsdError::ArithmeticOverflow)?;
+    require!(allocation_used <= allocation, NsdError::ExceedsAllocation);
+    
+    // Validate the mint against the remaining sale rules
+    // The public active flag does not gate the presale
+    ctx.accounts.config.validate_mint_limits(&ctx.accounts.user_account, phase, mint_amount, timestamp)?;
+    
+    // Calculate the total cost of minting at the phase price
+    let total_cost = ctx.accounts.config.quote(phase, mint_amount, timestamp)?;
+    
+    // Check the price has not moved past the buyer's limit
+    // The price can change between quoting and landing, e.g. through update_config
+    require!(total_cost <= max_total_cost, NsdError::PriceExceedsLimit);
+    
+    // Check if user has sufficient balance to pay for minting
+    // If the fee payer doesn't have enough lamports, throw an error
+    require!(
+        ctx.accounts.fee_payer.lamports() >= total_cost,
+        NsdError::InsufficientFunds
+    );
+    
+    // Collect payment
+    // Transfer the total cost from the fee payer into the treasury
+    ctx.accounts.cpi_system_transfer_to_treasury(total_cost)?;
+    
+    // Create the user's token account if needed
+    // Its address was checked against the user's associated token address
+    ctx.accounts.cpi_create_user_token_account()?;
+    
+    // Mint tokens to user
+    // Perform the CPI to mint tokens using the helper method
+    ctx.accounts.cpi_token_mint_to(mint_amount)?;
+    
+    // Update config with new minted amount and revenue
+    ctx.accounts.config.record_mint(mint_amount, total_cost)?;
+    
+    // Update user account
+    // Record the mint in the user's history
+    let user = ctx.accounts.user.key();
+    ctx.accounts.user_account.record_mint(user, mint_amount, timestamp, ctx.bumps.user_account)?;
+    
+    emit!(TokensMinted {
+        config: ctx.accounts.config.key(),
+        user,
+        amount: mint_amount,
+        price_paid: total_cost,
+        total_minted: ctx.accounts.config.total_minted,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for proposing a new admin
+/// 
+/// This struct defines the accounts needed for the propose_admin instruction.
+/// It includes the fee payer, configuration account and the current admin authority.
+#[derive(Accounts)]
+#[instruction(
+    new_admin: Pubkey,
+)]
+pub struct ProposeAdmin<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The current admin authority account
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Propose a new admin
+/// 
+/// This instruction records a pending admin on the configuration. The current admin
+/// stays in charge until the pending admin signs `accept_admin`. Proposing again
+/// replaces any earlier proposal.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `admin` - Signer - The current admin authority account
+/// 
+/// # Parameters
+/// - `new_admin` - The proposed admin authority
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<ProposeAdmin>,
+    new_admin: Pubkey,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Record the pending admin
+    ctx.accounts.config.pending_admin = Some(new_admin);
+    
+    emit!(AdminProposed {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+    });
+    
+    Ok(())
+}
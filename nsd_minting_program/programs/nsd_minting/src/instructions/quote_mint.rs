@@ -0,0 +1,39 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Quote the cost of a mint
+/// 
+/// This view instruction returns the lamports a mint of `mint_amount` tokens would charge at the
+/// current `Clock` time, computed by the same `NsdConfig::quote` as the mint instructions. Clients
+/// simulate it and pass the result as the mint's `max_total_cost`, instead of re-implementing the
+/// pricing. It reads the same accounts as the current_price view.
+/// 
+/// # Accounts
+/// - `config` - The configuration account
+/// - `sale_phase` - Optional - The active sale phase, required once phases are configured
+/// 
+/// # Parameters
+/// - `mint_amount` - Number of tokens to quote
+/// 
+/// # Returns
+/// - `Result<u64>` - Total cost of the mint in lamports
+pub fn handler(
+    ctx: Context<CurrentPrice>,
+    mint_amount: u64,
+) -> Result<u64> {
+    // Resolve the phase that would apply to a mint right now
+    let timestamp = Clock::get()?.unix_timestamp;
+    let phase = ctx.accounts.config.active_phase(ctx.accounts.sale_phase.as_deref(), timestamp)?;
+    
+    ctx.accounts.config.quote(phase, mint_amount, timestamp)
+}
@@ -0,0 +1,176 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Accounts required for selling NSD tokens back along the bonding curve
+/// 
+/// This struct defines the accounts needed for the sell_tokens instruction.
+/// It includes the seller, configuration account, treasury, token mint and the seller's token account.
+#[derive(Accounts)]
+#[instruction(
+    sell_amount: u64,
+)]
+pub struct SellTokens<'info> {
+    /// The user selling tokens, who receives the refund
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The treasury account paying the refund
+    /// Must be mutable and seeded with "treasury" and the config key
+    #[account(
+        mut,
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
+    /// The token mint account for NSD tokens
+    /// Must be mutable, match the mint stored in the configuration and be owned by the token program
+    #[account(
+        mut,
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The user's associated token account for the NSD mint
+    /// The sold tokens are burned from it
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Implementation of helper methods for SellTokens
+impl<'info> SellTokens<'info> {
+    /// CPI (Cross-Program Invocation) to burn tokens from the user's token account
+    /// 
+    /// The user signs the transaction as owner of the token account.
+    /// 
+    /// # Parameters
+    /// - `amount` - The amount of tokens to burn
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_burn(&self, amount: u64) -> Result<()> {
+        anchor_spl::token_interface::burn(
+            CpiContext::new(self.token_program.to_account_info(),
+                anchor_spl::token_interface::Burn {
+                    mint: self.token_mint.to_account_info(),
+                    from: self.user_token_account.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+/// Sell NSD tokens back to the treasury
+/// 
+/// This instruction burns tokens from the user's token account and pays them the integral of the
+/// bonding curve over the top of the supply they return, from the treasury. The refund of selling
+/// a range equals what minting it costs, so a round trip is neutral apart from fees. Selling
+/// follows `is_active` until the sale is finalized and stays open for good afterwards. Selling
+/// fails while the mint's supply exceeds `total_minted`, as the extra tokens have no reserve.
+/// 
+/// # Accounts
+/// - `user` - Writable, signer - The seller, receiving the refund
+/// - `config` - Writable - The configuration account
+/// - `treasury` - Writable - The treasury account paying the refund
+/// - `token_mint` - Writable - The configured NSD token mint
+/// - `user_token_account` - Writable - The user's associated token account, burned from
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
+/// 
+/// # Parameters
+/// - `sell_amount` - Number of tokens to sell
+/// - `min_lamports_out` - Smallest refund the user accepts, guarding against price moves
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<SellTokens>,
+    sell_amount: u64,
+    min_lamports_out: u64,
+) -> Result<()> {
+    // Check the sale is open
+    // Selling is paused together with minting, but stays open after finalization
+    // so holders can always sell back against the reserve left in the treasury
+    require!(
+        ctx.accounts.config.is_active || ctx.accounts.config.is_finalized,
+        NsdError::MintingNotActive
+    );
+    
+    // Check every token in circulation was minted by the sale
+    // Supply above total_minted has no reserve behind it, e.g. tokens minted before the
+    // mint authority was bound; supply below it only means holders burned tokens themselves
+    require!(
+        ctx.accounts.token_mint.supply <= ctx.accounts.config.total_minted,
+        NsdError::UnbackedMintSupply
+    );
+    
+    // Calculate the refund along the bonding curve
+    // Check the seller's slippage limit
+    let refund = ctx.accounts.config.quote_sell(sell_amount)?;
+    require!(refund >= min_lamports_out, NsdError::SlippageExceeded);
+    
+    // Check the treasury can pay the refund and stay rent-exempt
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let available = treasury_info.lamports().saturating_sub(rent_exempt_minimum);
+    require!(refund <= available, NsdError::InsufficientTreasuryBalance);
+    
+    // Burn the sold tokens
+    ctx.accounts.cpi_token_burn(sell_amount)?;
+    
+    // Pay the refund
+    // The treasury is owned by this program so its balance can be debited directly
+    ctx.accounts.treasury.sub_lamports(refund)?;
+    ctx.accounts.user.add_lamports(refund)?;
+    
+    // Update config with the returned supply and the refund
+    ctx.accounts.config.record_sell(sell_amount, refund)?;
+    
+    emit!(TokensSold {
+        config: ctx.accounts.config.key(),
+        user: ctx.accounts.user.key(),
+        amount: sell_amount,
+        refund,
+        total_minted: ctx.accounts.config.total_minted,
+    });
+    
+    Ok(())
+}
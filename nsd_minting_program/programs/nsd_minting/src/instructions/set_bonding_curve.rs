@@ -0,0 +1,108 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+/// Accounts required for setting the bonding curve
+/// 
+/// This struct defines the accounts needed for the set_bonding_curve instruction.
+/// It includes the fee payer, configuration account, token mint and admin authority.
+#[derive(Accounts)]
+pub struct SetBondingCurve<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The token mint account for NSD tokens
+    /// Must match the mint stored in the configuration, its supply is checked against the sale
+    #[account(
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The admin authority account
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Set the bonding curve
+/// 
+/// This instruction sets or clears the linear bonding curve. While set, the curve prices every
+/// mint by `total_minted`, replacing the mint price and any sale phase price, and holders can sell
+/// tokens back to the treasury along the same curve. Price tiers must be cleared first.
+/// 
+/// The curve is fixed once any token is minted: every lamport the treasury reserves for sells
+/// was paid in along the curve, so changing or clearing it could never be covered or would
+/// release the reserve. A curve also needs the whole supply of the mint to come from the sale,
+/// so tokens minted before the mint authority was bound cannot be sold against the reserve.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `token_mint` - The configured NSD token mint
+/// - `admin` - Signer - The admin authority account
+/// 
+/// # Parameters
+/// - `curve` - The new bonding curve, or `None` to return to the mint price
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<SetBondingCurve>,
+    curve: Option<BondingCurve>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Check nothing has been minted yet
+    // The reserve backing sells is only exact if every token was bought along this curve
+    require!(ctx.accounts.config.total_minted == 0, NsdError::BondingCurveLocked);
+    
+    // Check the mint holds no tokens from outside the sale
+    // An external mint can carry supply from before bind_mint_authority that was never paid in
+    if curve.is_some() {
+        require!(
+            ctx.accounts.token_mint.supply == ctx.accounts.config.total_minted,
+            NsdError::UnbackedMintSupply
+        );
+    }
+    
+    // Replace the curve and check it against the tiers and the price band
+    ctx.accounts.config.bonding_curve = curve;
+    ctx.accounts.config.validate()?;
+    
+    emit!(BondingCurveSet {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        curve,
+    });
+    
+    Ok(())
+}
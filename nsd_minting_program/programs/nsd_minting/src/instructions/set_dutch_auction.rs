@@ -0,0 +1,81 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for setting the Dutch auction
+/// 
+/// This struct defines the accounts needed for the set_dutch_auction instruction.
+/// It includes the fee payer, configuration account and admin authority.
+#[derive(Accounts)]
+pub struct SetDutchAuction<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The admin authority account
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Set the Dutch auction
+/// 
+/// This instruction sets or clears the Dutch auction. While set, every token of a mint costs the
+/// auction price at the current `Clock` time, replacing the mint price and any sale phase price.
+/// The price starts at `start_price`, decays linearly from `start_timestamp` over `decay_interval`
+/// seconds and stays at `floor_price` afterwards. Price tiers and the bonding curve must be cleared first.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `admin` - Signer - The admin authority account
+/// 
+/// # Parameters
+/// - `auction` - The new Dutch auction, or `None` to return to the mint price
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<SetDutchAuction>,
+    auction: Option<DutchAuction>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Replace the auction and check it against the other pricing modes and the price band
+    ctx.accounts.config.dutch_auction = auction;
+    ctx.accounts.config.validate()?;
+    
+    emit!(DutchAuctionSet {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        auction,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for setting the payment mints
+/// 
+/// This struct defines the accounts needed for the set_payment_mints instruction.
+/// It includes the fee payer, configuration account and admin authority.
+#[derive(Accounts)]
+pub struct SetPaymentMints<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The admin authority account
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Set the accepted payment mints
+/// 
+/// This instruction replaces the list of SPL tokens a sale accepts through `mint_tokens_with_token`,
+/// each with its own flat price per NSD token. An empty list accepts lamports only. The lamport
+/// pricing modes and the price band do not apply to token payments, and a bonding curve sale
+/// cannot accept tokens since its refunds are paid in lamports.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `admin` - Signer - The admin authority account
+/// 
+/// # Parameters
+/// - `payment_mints` - Accepted payment mints and their prices, at most `MAX_PAYMENT_MINTS`
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<SetPaymentMints>,
+    payment_mints: Vec<PaymentMint>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Replace the payment mints and check them
+    ctx.accounts.config.payment_mints = payment_mints;
+    ctx.accounts.config.validate()?;
+    
+    emit!(PaymentMintsSet {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        payment_mints: ctx.accounts.config.payment_mints.clone(),
+    });
+    
+    Ok(())
+}
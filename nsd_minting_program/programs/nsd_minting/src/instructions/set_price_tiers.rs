@@ -0,0 +1,81 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for setting the price tiers
+/// 
+/// This struct defines the accounts needed for the set_price_tiers instruction.
+/// It includes the fee payer, configuration account and admin authority.
+#[derive(Accounts)]
+pub struct SetPriceTiers<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The admin authority account
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Set the price tiers
+/// 
+/// This instruction replaces the sale's price-tier table. While tiers are set, each token is
+/// charged the price of the tier covering its position in the cumulative supply sold, and
+/// `mint_price` is ignored. An empty list restores the flat mint price. An active sale phase's
+/// price still takes precedence over the tiers.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `admin` - Signer - The admin authority account
+/// 
+/// # Parameters
+/// - `tiers` - Price tiers ordered by start supply, the first starting at zero
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<SetPriceTiers>,
+    tiers: Vec<PriceTier>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Replace the tiers and check them
+    ctx.accounts.config.price_tiers = tiers;
+    ctx.accounts.config.validate()?;
+    
+    emit!(PriceTiersSet {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        tiers: ctx.accounts.config.price_tiers.clone(),
+    });
+    
+    Ok(())
+}
@@ -10,17 +10,18 @@
 // Import required modules and macros
 use crate::*;
 use anchor_lang::prelude::*;
-use std::str::FromStr;
 
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    metadata::Metadata,
+    token_interface::{Mint, TokenInterface},
 };
 
 /// Accounts required for setting token metadata
 /// 
 /// This struct defines the accounts needed for the set_token_metadata instruction.
-/// It includes the fee payer, configuration account, metadata account, and admin authority.
+/// It includes the fee payer, configuration account, admin authority, and the accounts
+/// needed to write the token's public metadata: the metadata account and the Metaplex
+/// accounts for SPL Token mints, or the mint's own token-metadata extension for Token-2022 mints.
 #[derive(Accounts)]
 #[instruction(
     token_mint: Pubkey,
@@ -35,49 +36,194 @@ pub struct SetTokenMetadata<'info> {
     pub fee_payer: Signer<'info>,
 
     /// The configuration account that stores minting parameters
-    /// Must be mutable and seeded with "nsd_config"
+    /// Must be mutable and seeded with "nsd_config" and the token mint
     #[account(
         mut,
         seeds = [
-            b"nsd_config",
+            CONFIG_SEED,
+            token_mint.as_ref(),
         ],
-        bump,
+        bump = config.bump,
     )]
     pub config: Account<'info, NsdConfig>,
 
-    /// The metadata account that stores token metadata
+    /// The metadata account that stores token metadata, only required for SPL Token mints
     /// This account is initialized with specific space and seeds
     #[account(
         init,
-        space=295,
+        space=NsdTokenMetadata::space(&name, &symbol, &uri),
         payer=fee_payer,
         seeds = [
-            b"metadata",
+            METADATA_SEED,
             token_mint.as_ref(),
         ],
         bump,
     )]
-    pub metadata: Account<'info, NsdTokenMetadata>,
+    pub metadata: Option<Account<'info, NsdTokenMetadata>>,
 
     /// The admin authority account that can set metadata
     /// Must be a signer account
     pub admin: Signer<'info>,
 
+    /// The token mint account for NSD tokens
+    /// Must match the mint the metadata is set for and be owned by the token program
+    /// Writable so Token-2022 mints can store the metadata extension
+    #[account(
+        mut,
+        address = token_mint @ NsdError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The program PDA that holds the mint authority
+    /// Signs the metadata CPI as mint and update authority
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.mint_authority_bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The Metaplex metadata account for the token mint
+    /// Created by the Token Metadata program, only required for SPL Token mints
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            anchor_spl::metadata::ID.as_ref(),
+            token_mint.as_ref(),
+        ],
+        seeds::program = anchor_spl::metadata::ID,
+        bump,
+    )]
+    /// CHECK: initialized and validated by the Token Metadata program
+    pub metaplex_metadata: Option<UncheckedAccount<'info>>,
+
+    /// The Metaplex Token Metadata program, only required for SPL Token mints
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
     /// The system program account for account initialization
     pub system_program: Program<'info, System>,
+
+    /// The rent sysvar, required by the Token Metadata program
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Implementation of helper methods for SetTokenMetadata
+impl<'info> SetTokenMetadata<'info> {
+    /// CPI to the Token Metadata program to create the Metaplex metadata account
+    /// 
+    /// The mint authority PDA signs as mint authority and becomes the update authority.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_metaplex_create_metadata(&self) -> Result<()> {
+        let (Some(metadata), Some(metaplex_metadata), Some(token_metadata_program)) =
+            (&self.metadata, &self.metaplex_metadata, &self.token_metadata_program)
+        else {
+            return err!(NsdError::MissingMetadataAccounts);
+        };
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED,
+            config_key.as_ref(),
+            &[self.config.mint_authority_bump],
+        ]];
+        anchor_spl::metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(token_metadata_program.to_account_info(),
+                anchor_spl::metadata::CreateMetadataAccountsV3 {
+                    metadata: metaplex_metadata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    mint_authority: self.mint_authority.to_account_info(),
+                    payer: self.fee_payer.to_account_info(),
+                    update_authority: self.mint_authority.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            metadata.metaplex_data(),
+            true,
+            true,
+            None,
+        )
+    }
+
+    /// CPI to the Token-2022 program to write the metadata into the mint's token-metadata extension
+    /// 
+    /// The fee payer first funds the rent for the larger mint account. The mint authority PDA
+    /// signs as mint authority and becomes the update authority.
+    /// 
+    /// # Parameters
+    /// - `name` - Name of the token
+    /// - `symbol` - Symbol of the token
+    /// - `uri` - URI for token metadata
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_metadata_initialize(&self, name: &str, symbol: &str, uri: &str) -> Result<()> {
+        let token_metadata = token_extensions::token_metadata(
+            self.mint.key(),
+            self.mint_authority.key(),
+            name,
+            symbol,
+            uri,
+        )?;
+        token_extensions::fund_metadata_rent(
+            &self.fee_payer.to_account_info(),
+            &self.mint.to_account_info(),
+            &self.system_program.to_account_info(),
+            &token_metadata,
+        )?;
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED,
+            config_key.as_ref(),
+            &[self.config.mint_authority_bump],
+        ]];
+        anchor_spl::token_2022_extensions::token_metadata_initialize(
+            CpiContext::new_with_signer(self.token_program.to_account_info(),
+                anchor_spl::token_2022_extensions::TokenMetadataInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: self.mint.to_account_info(),
+                    update_authority: self.mint_authority.to_account_info(),
+                    mint_authority: self.mint_authority.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_metadata.name,
+            token_metadata.symbol,
+            token_metadata.uri,
+        )
+    }
 }
 
 /// Set metadata for NSD tokens
 /// 
 /// This instruction sets the metadata for NSD tokens including name, symbol, and URI.
-/// It ensures only the admin can set metadata.
+/// It ensures only the admin can set metadata, and publishes it so wallets and explorers display
+/// the token: Token-2022 mints store it in their own token-metadata extension only, while SPL Token
+/// mints get the program's metadata account and a matching Metaplex metadata account.
 /// 
 /// # Accounts
 /// - `fee_payer` - Writable, signer - The account paying for transaction fees
 /// - `config` - Writable - The configuration account
-/// - `metadata` - Writable - The metadata account to be initialized
+/// - `metadata` - Optional, writable - The metadata account to be initialized for SPL Token mints
 /// - `admin` - Signer - The admin authority account
+/// - `mint` - Writable - The NSD token mint account
+/// - `mint_authority` - PDA - The program's mint authority, signs the metadata CPI
+/// - `metaplex_metadata` - Optional, writable - The Metaplex metadata account to be created for SPL Token mints
+/// - `token_metadata_program` - Optional - The Metaplex Token Metadata program for SPL Token mints
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
 /// - `system_program` - System program - Required for account initialization
+/// - `rent` - Rent sysvar - Required by the Token Metadata program
 /// 
 /// # Parameters
 /// - `token_mint` - Mint address for NSD token
@@ -98,17 +244,47 @@ pub fn handler(
     // Verify that the caller is the admin authority
     require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
     
-    // Set metadata values
-    // Store the token mint address
-    ctx.accounts.metadata.mint = token_mint;
-    // Store the token name
-    ctx.accounts.metadata.name = name;
-    // Store the token symbol
-    ctx.accounts.metadata.symbol = symbol;
-    // Store the token URI
-    ctx.accounts.metadata.uri = uri;
-    // Store the bump seed for the metadata account
-    ctx.accounts.metadata.bump = ctx.bumps.metadata;
+    // Check the mint authority
+    // The mint authority PDA must hold the mint authority to sign the metadata CPI
+    require!(ctx.accounts.config.mint_authority_bound, NsdError::MintAuthorityNotBound);
+    
+    // Check the metadata lengths
+    // Reject strings over the documented maximum lengths
+    NsdTokenMetadata::validate(&name, &symbol, &uri)?;
+    
+    // Publish the metadata
+    // Token-2022 mints hold it natively, without the metadata account
+    if token_extensions::is_token_2022(&ctx.accounts.mint.to_account_info()) {
+        require!(ctx.accounts.metadata.is_none(), NsdError::UnexpectedMetadataAccount);
+        ctx.accounts.cpi_token_metadata_initialize(&name, &symbol, &uri)?;
+    } else {
+        // SPL Token mints store it in the metadata account, mirrored by a Metaplex metadata account
+        let (Some(metadata), Some(bump)) = (ctx.accounts.metadata.as_mut(), ctx.bumps.metadata) else {
+            return err!(NsdError::MissingMetadataAccounts);
+        };
+        // Store the token mint address
+        metadata.mint = token_mint;
+        // Store the token name
+        metadata.name = name.clone();
+        // Store the token symbol
+        metadata.symbol = symbol.clone();
+        // Store the token URI
+        metadata.uri = uri.clone();
+        // Store the bump seed for the metadata account
+        metadata.bump = bump;
+        // Start with the metadata editable
+        metadata.is_locked = false;
+        
+        ctx.accounts.cpi_metaplex_create_metadata()?;
+    }
+    
+    emit!(MetadataSet {
+        config: ctx.accounts.config.key(),
+        mint: token_mint,
+        name,
+        symbol,
+        uri,
+    });
     
     Ok(())
 }
\ No newline at end of file
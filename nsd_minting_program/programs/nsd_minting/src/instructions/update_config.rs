@@ -10,12 +10,33 @@
 // Import required modules and macros
 use crate::*;
 use anchor_lang::prelude::*;
-use std::str::FromStr;
 
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
-};
+/// Arguments for updating the NSD minting configuration
+/// 
+/// Every field is optional; fields left as `None` keep their current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigArgs {
+    /// New maximum supply
+    pub max_supply: Option<u64>,
+    
+    /// New mint price
+    pub mint_price: Option<u64>,
+    
+    /// New active status
+    pub is_active: Option<bool>,
+    
+    /// New per-wallet mint cap, `Some(0)` removes the cap
+    pub max_per_wallet: Option<u64>,
+    
+    /// New maximum number of tokens per mint transaction, `Some(0)` removes the limit
+    pub max_per_transaction: Option<u64>,
+    
+    /// New cooldown between mints from the same wallet, in seconds
+    pub cooldown_seconds: Option<u64>,
+    
+    /// New presale allowlist Merkle root, all zeroes closes the presale
+    pub allowlist_root: Option<[u8; 32]>,
+}
 
 /// Accounts required for updating the NSD minting configuration
 /// 
@@ -23,9 +44,7 @@ use anchor_spl::{
 /// It includes the fee payer, configuration account, and admin authority.
 #[derive(Accounts)]
 #[instruction(
-    max_supply: Option<u64>,
-    mint_price: Option<u64>,
-    is_active: Option<bool>,
+    args: UpdateConfigArgs,
 )]
 pub struct UpdateConfig<'info> {
     /// The fee payer for account initialization
@@ -34,13 +53,14 @@ pub struct UpdateConfig<'info> {
     pub fee_payer: Signer<'info>,
 
     /// The configuration account that stores minting parameters
-    /// Must be mutable and seeded with "nsd_config"
+    /// Must be mutable and seeded with "nsd_config" and the token mint
     #[account(
         mut,
         seeds = [
-            b"nsd_config",
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
         ],
-        bump,
+        bump = config.bump,
     )]
     pub config: Account<'info, NsdConfig>,
 
@@ -52,7 +72,9 @@ pub struct UpdateConfig<'info> {
 /// Update NSD minting configuration
 /// 
 /// This instruction updates the configuration parameters for NSD token minting.
-/// It allows updating maximum supply, mint price, and active status.
+/// It allows updating maximum supply, mint price, active status and per-wallet limits.
+/// The maximum supply cannot go below the total minted or, once frozen, above its current value,
+/// and the mint price must stay within the price band set at initialization.
 /// 
 /// # Accounts
 /// - `fee_payer` - Writable, signer - The account paying for transaction fees
@@ -60,39 +82,88 @@ pub struct UpdateConfig<'info> {
 /// - `admin` - Signer - The admin authority account
 /// 
 /// # Parameters
-/// - `max_supply` - New maximum supply (optional)
-/// - `mint_price` - New mint price (optional)
-/// - `is_active` - New active status (optional)
+/// - `args` - The configuration fields to update
+///   - `max_supply` - New maximum supply (optional)
+///   - `mint_price` - New mint price (optional)
+///   - `is_active` - New active status (optional)
+///   - `max_per_wallet` - New per-wallet mint cap, `Some(0)` removes the cap (optional)
+///   - `max_per_transaction` - New per-transaction mint limit, `Some(0)` removes the limit (optional)
+///   - `cooldown_seconds` - New cooldown between mints from one wallet (optional)
+///   - `allowlist_root` - New presale allowlist root, all zeroes closes the presale (optional)
 /// 
 /// # Returns
 /// - `Result<()>` - Success or error
 pub fn handler(
     ctx: Context<UpdateConfig>,
-    max_supply: Option<u64>,
-    mint_price: Option<u64>,
-    is_active: Option<bool>,
+    args: UpdateConfigArgs,
 ) -> Result<()> {
     // Check if admin is the owner
     // Verify that the caller is the admin authority
     require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
     
+    // Check the sale has not been finalized
+    // A finalized configuration is permanently read-only
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Snapshot the settings before the update
+    let old = ctx.accounts.config.settings();
+    
     // Update max supply if provided
     // If a new max supply is provided, update the configuration
-    if let Some(supply) = max_supply {
+    // A frozen supply can still be lowered but never raised
+    if let Some(supply) = args.max_supply {
+        require!(
+            !ctx.accounts.config.max_supply_frozen || supply <= ctx.accounts.config.max_supply,
+            NsdError::MaxSupplyFrozen
+        );
         ctx.accounts.config.max_supply = supply;
     }
     
     // Update mint price if provided
     // If a new mint price is provided, update the configuration
-    if let Some(price) = mint_price {
+    if let Some(price) = args.mint_price {
         ctx.accounts.config.mint_price = price;
     }
     
     // Update active status if provided
     // If a new active status is provided, update the configuration
-    if let Some(active) = is_active {
+    if let Some(active) = args.is_active {
         ctx.accounts.config.is_active = active;
     }
     
+    // Update per-wallet cap if provided
+    // A cap of zero removes the per-wallet limit
+    if let Some(cap) = args.max_per_wallet {
+        ctx.accounts.config.max_per_wallet = if cap == 0 { None } else { Some(cap) };
+    }
+    
+    // Update per-transaction limit if provided
+    // A limit of zero removes the per-transaction limit
+    if let Some(limit) = args.max_per_transaction {
+        ctx.accounts.config.max_per_transaction = if limit == 0 { u64::MAX } else { limit };
+    }
+    
+    // Update cooldown if provided
+    // If a new cooldown is provided, update the configuration
+    if let Some(cooldown) = args.cooldown_seconds {
+        ctx.accounts.config.cooldown_seconds = cooldown;
+    }
+    
+    // Update presale allowlist root if provided
+    // An all-zero root closes the presale
+    if let Some(root) = args.allowlist_root {
+        ctx.accounts.config.allowlist_root = if root == [0u8; 32] { None } else { Some(root) };
+    }
+    
+    // Check the updated configuration
+    ctx.accounts.config.validate()?;
+    
+    emit!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.admin.key(),
+        old,
+        new: ctx.accounts.config.settings(),
+    });
+    
     Ok(())
 }
\ No newline at end of file
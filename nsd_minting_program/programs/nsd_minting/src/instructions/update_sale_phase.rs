@@ -0,0 +1,154 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for updating a sale phase
+/// 
+/// This struct defines the accounts needed for the update_sale_phase instruction.
+/// It includes the fee payer, configuration account, the sale phase and the admin authority.
+#[derive(Accounts)]
+#[instruction(
+    start_timestamp: Option<i64>,
+    end_timestamp: Option<i64>,
+    price: Option<u64>,
+    max_per_wallet: Option<u64>,
+    allowlist_root: Option<[u8; 32]>,
+)]
+pub struct UpdateSalePhase<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Seeded with "nsd_config" and the token mint
+    #[account(
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The sale phase account to update
+    /// Must belong to this configuration
+    #[account(
+        mut,
+        seeds = [
+            SALE_PHASE_SEED,
+            config.key().as_ref(),
+            sale_phase.phase_id.to_le_bytes().as_ref(),
+        ],
+        bump = sale_phase.bump,
+    )]
+    pub sale_phase: Account<'info, SalePhase>,
+
+    /// The admin authority account that can manage sale phases
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+}
+
+/// Update a sale phase
+/// 
+/// This instruction updates the window, price and limits of an existing sale phase.
+/// A moved window may not overlap the window of any other phase.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - The configuration account
+/// - `sale_phase` - Writable - The sale phase account to update
+/// - `admin` - Signer - The admin authority account
+/// - Remaining accounts - Every other sale phase of the configuration, when the window changes
+/// 
+/// # Parameters
+/// - `start_timestamp` - New opening timestamp (optional)
+/// - `end_timestamp` - New closing timestamp (optional)
+/// - `price` - New price per token in lamports (optional)
+/// - `max_per_wallet` - New per-wallet cap, `Some(0)` removes the cap (optional)
+/// - `allowlist_root` - New allowlist root, all zeroes removes the allowlist (optional)
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<UpdateSalePhase>,
+    start_timestamp: Option<i64>,
+    end_timestamp: Option<i64>,
+    price: Option<u64>,
+    max_per_wallet: Option<u64>,
+    allowlist_root: Option<[u8; 32]>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the sale has not been finalized
+    ctx.accounts.config.require_not_finalized()?;
+    
+    // Update the phase window if provided
+    if let Some(start) = start_timestamp {
+        ctx.accounts.sale_phase.start_timestamp = start;
+    }
+    if let Some(end) = end_timestamp {
+        ctx.accounts.sale_phase.end_timestamp = end;
+    }
+    // The phase must still open before it closes
+    require!(
+        ctx.accounts.sale_phase.start_timestamp < ctx.accounts.sale_phase.end_timestamp,
+        NsdError::InvalidPhaseWindow
+    );
+    
+    // Check a moved window against every other phase
+    // An unchanged window cannot overlap, the windows were checked when they were set
+    if start_timestamp.is_some() || end_timestamp.is_some() {
+        let expected = (ctx.accounts.config.phase_count as usize).saturating_sub(1);
+        ctx.accounts.config.require_phase_window_free(
+            &ctx.accounts.config.key(),
+            &ctx.accounts.sale_phase.key(),
+            ctx.accounts.sale_phase.start_timestamp,
+            ctx.accounts.sale_phase.end_timestamp,
+            ctx.remaining_accounts,
+            expected,
+        )?;
+    }
+    
+    // Update the phase price if provided
+    // The phase price must lie within the configured price band
+    if let Some(new_price) = price {
+        ctx.accounts.config.require_price_in_band(new_price)?;
+        ctx.accounts.sale_phase.price = new_price;
+    }
+    
+    // Update the phase per-wallet cap if provided
+    // A cap of zero removes the per-wallet limit
+    if let Some(cap) = max_per_wallet {
+        ctx.accounts.sale_phase.max_per_wallet = if cap == 0 { None } else { Some(cap) };
+    }
+    
+    // Update the phase allowlist root if provided
+    // An all-zero root opens the phase to public mints
+    if let Some(root) = allowlist_root {
+        ctx.accounts.sale_phase.allowlist_root = if root == [0u8; 32] { None } else { Some(root) };
+    }
+    
+    emit!(SalePhaseSet {
+        config: ctx.accounts.config.key(),
+        phase_id: ctx.accounts.sale_phase.phase_id,
+        start_timestamp: ctx.accounts.sale_phase.start_timestamp,
+        end_timestamp: ctx.accounts.sale_phase.end_timestamp,
+        price: ctx.accounts.sale_phase.price,
+        max_per_wallet: ctx.accounts.sale_phase.max_per_wallet,
+        allowlist_root: ctx.accounts.sale_phase.allowlist_root,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,278 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::Field;
+
+use anchor_spl::{
+    metadata::Metadata,
+    token_interface::{Mint, TokenInterface},
+};
+
+/// Accounts required for updating token metadata
+/// 
+/// This struct defines the accounts needed for the update_token_metadata instruction.
+/// It includes the fee payer, configuration account, admin authority, and the accounts
+/// needed to update the token's public metadata: the metadata account and the Metaplex
+/// accounts for SPL Token mints, or the mint's own token-metadata extension for Token-2022 mints.
+/// The metadata account is reallocated to fit the new string values.
+#[derive(Accounts)]
+#[instruction(
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+)]
+pub struct UpdateTokenMetadata<'info> {
+    /// The fee payer for the transaction and any additional rent
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Seeded with "nsd_config" and the token mint
+    #[account(
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The metadata account that stores token metadata, only required for SPL Token mints
+    /// Reallocated to the size of the updated values
+    #[account(
+        mut,
+        seeds = [
+            METADATA_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = metadata.bump,
+        realloc = NsdTokenMetadata::space(
+            name.as_deref().unwrap_or(&metadata.name),
+            symbol.as_deref().unwrap_or(&metadata.symbol),
+            uri.as_deref().unwrap_or(&metadata.uri),
+        ),
+        realloc::payer = fee_payer,
+        realloc::zero = false,
+    )]
+    pub metadata: Option<Account<'info, NsdTokenMetadata>>,
+
+    /// The admin authority account that can update metadata
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The program PDA that holds the mint authority
+    /// Signs the Metaplex CPI as update authority
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.mint_authority_bump,
+    )]
+    /// CHECK: PDA signer only, verified by seeds
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The token mint account for NSD tokens
+    /// Must match the configured mint and be owned by the token program
+    /// Writable so Token-2022 mints can update the metadata extension
+    #[account(
+        mut,
+        address = config.token_mint @ NsdError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The Metaplex metadata account for the token mint, only required for SPL Token mints
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            anchor_spl::metadata::ID.as_ref(),
+            config.token_mint.as_ref(),
+        ],
+        seeds::program = anchor_spl::metadata::ID,
+        bump,
+    )]
+    /// CHECK: validated by the Token Metadata program
+    pub metaplex_metadata: Option<UncheckedAccount<'info>>,
+
+    /// The Metaplex Token Metadata program, only required for SPL Token mints
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program account for reallocation
+    pub system_program: Program<'info, System>,
+}
+
+/// Implementation of helper methods for UpdateTokenMetadata
+impl<'info> UpdateTokenMetadata<'info> {
+    /// CPI to the Token-2022 program to update the mint's token-metadata extension
+    /// 
+    /// The fee payer first funds any additional rent for the resized mint account.
+    /// The mint authority PDA signs as update authority.
+    /// 
+    /// # Parameters
+    /// - `name` - Name of the token
+    /// - `symbol` - Symbol of the token
+    /// - `uri` - URI for token metadata
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_metadata_update_fields(&self, name: &str, symbol: &str, uri: &str) -> Result<()> {
+        let token_metadata = token_extensions::token_metadata(
+            self.mint.key(),
+            self.mint_authority.key(),
+            name,
+            symbol,
+            uri,
+        )?;
+        token_extensions::fund_metadata_rent(
+            &self.fee_payer.to_account_info(),
+            &self.mint.to_account_info(),
+            &self.system_program.to_account_info(),
+            &token_metadata,
+        )?;
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED,
+            config_key.as_ref(),
+            &[self.config.mint_authority_bump],
+        ]];
+        let fields = [
+            (Field::Name, token_metadata.name),
+            (Field::Symbol, token_metadata.symbol),
+            (Field::Uri, token_metadata.uri),
+        ];
+        for (field, value) in fields {
+            anchor_spl::token_2022_extensions::token_metadata_update_field(
+                CpiContext::new_with_signer(self.token_program.to_account_info(),
+                    anchor_spl::token_2022_extensions::TokenMetadataUpdateField {
+                        program_id: self.token_program.to_account_info(),
+                        metadata: self.mint.to_account_info(),
+                        update_authority: self.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                field,
+                value,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Update metadata for NSD tokens
+/// 
+/// This instruction updates the name, symbol and URI of the token metadata.
+/// Fields passed as `None` are left unchanged. Token-2022 mints update their token-metadata
+/// extension in place. SPL Token mints update the metadata account, resized to fit the new values
+/// with the fee payer covering extra rent or receiving the excess, and the Metaplex metadata
+/// account to match.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees and rent
+/// - `config` - The configuration account
+/// - `metadata` - Optional, writable - The metadata account to update for SPL Token mints
+/// - `admin` - Signer - The admin authority account
+/// - `mint_authority` - PDA - The program's mint authority, signs the metadata CPI
+/// - `mint` - Writable - The NSD token mint account
+/// - `metaplex_metadata` - Optional, writable - The Metaplex metadata account to update for SPL Token mints
+/// - `token_metadata_program` - Optional - The Metaplex Token Metadata program for SPL Token mints
+/// - `token_program` - Token program - SPL Token or Token-2022, matching the mint
+/// - `system_program` - System program - Required for reallocation
+/// 
+/// # Parameters
+/// - `name` - New name of the token (optional)
+/// - `symbol` - New symbol of the token (optional)
+/// - `uri` - New URI for token metadata (optional)
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<UpdateTokenMetadata>,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    let (name, symbol, uri) = if token_extensions::is_token_2022(&ctx.accounts.mint.to_account_info()) {
+        // Token-2022 mints hold the metadata natively, without the metadata account
+        require!(ctx.accounts.metadata.is_none(), NsdError::UnexpectedMetadataAccount);
+        let current = token_extensions::read_token_metadata(&ctx.accounts.mint.to_account_info())?;
+        
+        // Check the metadata is still editable
+        // Locking removes the extension's update authority
+        require!(Option::<Pubkey>::from(current.update_authority).is_some(), NsdError::MetadataLocked);
+        
+        // Update the values if provided
+        let name = name.unwrap_or(current.name);
+        let symbol = symbol.unwrap_or(current.symbol);
+        let uri = uri.unwrap_or(current.uri);
+        
+        // Check the metadata lengths
+        // Reject strings over the documented maximum lengths
+        NsdTokenMetadata::validate(&name, &symbol, &uri)?;
+        
+        ctx.accounts.cpi_token_metadata_update_fields(&name, &symbol, &uri)?;
+        (name, symbol, uri)
+    } else {
+        let Some(metadata) = ctx.accounts.metadata.as_mut() else {
+            return err!(NsdError::MissingMetadataAccounts);
+        };
+        
+        // Check the metadata is still editable
+        require!(!metadata.is_locked, NsdError::MetadataLocked);
+        
+        // Update the values if provided
+        if let Some(name) = name {
+            metadata.name = name;
+        }
+        if let Some(symbol) = symbol {
+            metadata.symbol = symbol;
+        }
+        if let Some(uri) = uri {
+            metadata.uri = uri;
+        }
+        
+        // Check the metadata lengths
+        // Reject strings over the documented maximum lengths
+        NsdTokenMetadata::validate(&metadata.name, &metadata.symbol, &metadata.uri)?;
+        let values = (metadata.name.clone(), metadata.symbol.clone(), metadata.uri.clone());
+        
+        // Keep the Metaplex metadata account in sync with this one
+        metaplex::update_metadata(
+            &ctx.accounts.config,
+            &ctx.accounts.mint_authority.to_account_info(),
+            ctx.accounts.metadata.as_ref(),
+            ctx.accounts.metaplex_metadata.as_ref(),
+            ctx.accounts.token_metadata_program.as_ref(),
+            true,
+        )?;
+        values
+    };
+    
+    emit!(MetadataSet {
+        config: ctx.accounts.config.key(),
+        mint: ctx.accounts.config.token_mint,
+        name,
+        symbol,
+        uri,
+    });
+    
+    Ok(())
+}
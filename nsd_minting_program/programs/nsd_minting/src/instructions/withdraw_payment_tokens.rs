@@ -0,0 +1,167 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Accounts required for withdrawing payment tokens from the treasury
+/// 
+/// This struct defines the accounts needed for the withdraw_payment_tokens instruction.
+/// It includes the fee payer, configuration account, treasury, admin authority, the payment mint
+/// and the source and destination token accounts.
+#[derive(Accounts)]
+#[instruction(
+    amount: u64,
+)]
+pub struct WithdrawPaymentTokens<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Seeded with "nsd_config" and the token mint
+    #[account(
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The treasury account, which owns the treasury payment token account
+    /// Seeded with "treasury" and the config key
+    #[account(
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
+    /// The admin authority account that can withdraw funds
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The mint of the payment token to withdraw
+    /// Must be owned by the payment token program
+    #[account(
+        mint::token_program = payment_token_program,
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    /// The treasury's associated token account for the payment mint
+    /// Must be mutable and belong to the treasury
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub treasury_payment_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token account receiving the withdrawn tokens
+    /// Must hold the payment mint
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::token_program = payment_token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token program owning the payment mint, either SPL Token or Token-2022
+    pub payment_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Implementation of helper methods for WithdrawPaymentTokens
+impl<'info> WithdrawPaymentTokens<'info> {
+    /// CPI to the payment token program to move tokens out of the treasury token account
+    /// 
+    /// The treasury PDA signs the transfer with its seeds.
+    /// 
+    /// # Parameters
+    /// - `amount` - The amount of payment tokens to transfer, in base units
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cpi_token_transfer_from_treasury(&self, amount: u64) -> Result<()> {
+        let config_key = self.config.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            TREASURY_SEED,
+            config_key.as_ref(),
+            &[self.config.treasury_bump],
+        ]];
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(self.payment_token_program.to_account_info(),
+                anchor_spl::token_interface::TransferChecked {
+                    from: self.treasury_payment_account.to_account_info(),
+                    mint: self.payment_mint.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.treasury.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.payment_mint.decimals,
+        )
+    }
+}
+
+/// Withdraw payment tokens from the treasury
+/// 
+/// This instruction moves SPL tokens collected by mint_tokens_with_token from the treasury's
+/// associated token account to a destination token account. It ensures only the admin can withdraw.
+/// Tokens of a mint that is no longer accepted can still be withdrawn, including after the sale is finalized.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - The configuration account
+/// - `treasury` - PDA - The treasury account, signs the transfer
+/// - `admin` - Signer - The admin authority account
+/// - `payment_mint` - Mint - The payment token to withdraw
+/// - `treasury_payment_account` - Writable - The treasury's associated token account for the payment mint
+/// - `destination` - Writable - The token account receiving the tokens
+/// - `payment_token_program` - Token program - SPL Token or Token-2022, matching the payment mint
+/// 
+/// # Parameters
+/// - `amount` - Number of tokens to withdraw, in base units
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<WithdrawPaymentTokens>,
+    amount: u64,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the treasury token account can cover the withdrawal
+    require!(
+        amount <= ctx.accounts.treasury_payment_account.amount,
+        NsdError::InsufficientTreasuryBalance
+    );
+    
+    // Move the tokens out of the treasury token account
+    ctx.accounts.cpi_token_transfer_from_treasury(amount)?;
+    
+    emit!(PaymentTokensWithdrawn {
+        config: ctx.accounts.config.key(),
+        payment_mint: ctx.accounts.payment_mint.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+    
+    Ok(())
+}
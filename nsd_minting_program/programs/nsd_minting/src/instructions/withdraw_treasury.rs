@@ -0,0 +1,117 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+
+/// Accounts required for withdrawing from the treasury
+/// 
+/// This struct defines the accounts needed for the withdraw_treasury instruction.
+/// It includes the fee payer, configuration account, treasury, admin authority and destination.
+#[derive(Accounts)]
+#[instruction(
+    amount: u64,
+)]
+pub struct WithdrawTreasury<'info> {
+    /// The fee payer for the transaction
+    /// Must be a writable signer account
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// The configuration account that stores minting parameters
+    /// Must be mutable and seeded with "nsd_config" and the token mint
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED,
+            config.token_mint.as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, NsdConfig>,
+
+    /// The treasury account holding the mint proceeds
+    /// Must be mutable and seeded with "treasury" and the config key
+    #[account(
+        mut,
+        seeds = [
+            TREASURY_SEED,
+            config.key().as_ref(),
+        ],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: Account<'info, NsdTreasury>,
+
+    /// The admin authority account that can withdraw funds
+    /// Must be a signer account
+    pub admin: Signer<'info>,
+
+    /// The account receiving the withdrawn lamports
+    #[account(mut)]
+    /// CHECK: any account chosen by the admin may receive lamports
+    pub destination: UncheckedAccount<'info>,
+}
+
+/// Withdraw lamports from the treasury
+/// 
+/// This instruction moves collected mint proceeds from the treasury to a destination account.
+/// It ensures only the admin can withdraw and that the treasury stays rent-exempt. While a bonding curve
+/// is set, the lamports needed to buy back the whole minted supply along the curve stay in the treasury.
+/// 
+/// # Accounts
+/// - `fee_payer` - Writable, signer - The account paying for transaction fees
+/// - `config` - Writable - The configuration account
+/// - `treasury` - Writable - The treasury account to withdraw from
+/// - `admin` - Signer - The admin authority account
+/// - `destination` - Writable - The account receiving the lamports
+/// 
+/// # Parameters
+/// - `amount` - Number of lamports to withdraw
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn handler(
+    ctx: Context<WithdrawTreasury>,
+    amount: u64,
+) -> Result<()> {
+    // Check if admin is the owner
+    // Verify that the caller is the admin authority
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), NsdError::Unauthorized);
+    
+    // Check the treasury can cover the withdrawal
+    // The treasury must keep enough lamports to remain rent-exempt and to buy back the bonding-curve supply
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let reserve = ctx.accounts.config.curve_reserve()?;
+    let available = treasury_info.lamports()
+        .saturating_sub(rent_exempt_minimum)
+        .saturating_sub(reserve);
+    require!(amount <= available, NsdError::InsufficientTreasuryBalance);
+    
+    // Move lamports out of the treasury
+    // The treasury is owned by this program so its balance can be debited directly
+    ctx.accounts.treasury.sub_lamports(amount)?;
+    ctx.accounts.destination.add_lamports(amount)?;
+    
+    // Update revenue accounting
+    // Record the lamports withdrawn in the configuration
+    ctx.accounts.config.total_withdrawn = ctx.accounts.config.total_withdrawn
+        .checked_add(amount)
+        .ok_or(NsdError::ArithmeticOverflow)?;
+    
+    emit!(TreasuryWithdrawn {
+        config: ctx.accounts.config.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        total_withdrawn: ctx.accounts.config.total_withdrawn,
+    });
+    
+    Ok(())
+}
@@ -10,6 +10,21 @@
 // Import required modules and macros
 use anchor_lang::prelude::*;
 
+pub mod constants;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod merkle;
+pub mod metaplex;
+pub mod token_extensions;
+pub mod state;
+
+pub use constants::*;
+pub use error::*;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
+
 /// Program ID declaration
 /// 
 /// This declares the program's unique identifier on the Solana blockchain.
@@ -32,53 +47,63 @@ pub mod nsd_minting {
     /// # Accounts
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
     /// 1. `[writable]` config: [NsdConfig] 
-    /// 2. `[signer]` admin: [AccountInfo] Admin authority account
-    /// 3. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury collecting mint proceeds
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
     /// 
     /// # Data
     /// - token_mint: [Pubkey] Mint address for NSD token
     /// - max_supply: [u64] Maximum supply of NSD tokens
     /// - mint_price: [u64] Price per NSD token in lamports
+    /// - min_price: [Option<u64>] Lowest price the admin may set, if bounded
+    /// - max_price: [Option<u64>] Highest price the admin may set, if bounded
     /// 
     /// # Returns
     /// - `Result<()>` - Success or error
-    pub fn initialize_config(ctx: Context<InitializeConfig>, token_mint: Pubkey, max_supply: u64, mint_price: u64) -> Result<()> {
-        initialize_config::handler(ctx, token_mint, max_supply, mint_price)
+    pub fn initialize_config(ctx: Context<InitializeConfig>, token_mint: Pubkey, max_supply: u64, mint_price: u64, min_price: Option<u64>, max_price: Option<u64>) -> Result<()> {
+        initialize_config::handler(ctx, token_mint, max_supply, mint_price, min_price, max_price)
     }
 
     /// Mint NSD tokens for a user
     /// 
     /// This instruction mints NSD tokens to a user's associated token account.
-    /// It performs validation checks before minting and updates the configuration.
+    /// The mint CPI is signed by the program's mint authority PDA, so no external co-signer is needed.
+    /// It collects the mint price into the treasury before minting and updates the configuration.
+    /// This is the legacy account layout; new clients should use `mint_tokens_v2`.
     /// 
     /// # Accounts
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
     /// 1. `[writable]` config: [NsdConfig] 
-    /// 2. `[writable]` user_token_account: [AccountInfo] User's token account
-    /// 3. `[signer]` user: [AccountInfo] User's wallet address
-    /// 4. `[writable]` user_account: [NsdUser] 
-    /// 5. `[]` token_mint: [Mint] NSD token mint account
-    /// 6. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
-    /// 7. `[writable]` mint: [Mint] The mint.
-    /// 8. `[writable]` assoc_token_account: [Account] The account to mint tokens to.
-    /// 9. `[signer]` owner: [AccountInfo] The mint's minting authority.
-    /// 10. `[]` wallet: [AccountInfo] Wallet address for the new associated token account
-    /// 11. `[]` token_program: [AccountInfo] SPL Token program
-    /// 12. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury receiving the mint price
+    /// 3. `[writable]` user_token_account: [AccountInfo] Ignored, kept so the later account indices don't shift
+    /// 4. `[signer]` user: [AccountInfo] User's wallet address
+    /// 5. `[writable]` user_account: [NsdUser] 
+    /// 6. `[]` token_mint: [Mint] NSD token mint account
+    /// 7. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 8. `[writable]` mint: [Mint] The mint.
+    /// 9. `[writable]` assoc_token_account: [Account] The account to mint tokens to, created if needed.
+    /// 10. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 11. `[]` wallet: [AccountInfo] Wallet address for the new associated token account
+    /// 12. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 13. `[]` associated_token_program: [AccountInfo] Associated Token program
+    /// 14. `[]` sale_phase: [SalePhase] Optional, the active sale phase once phases are configured
     /// 
     /// # Data
     /// - mint_amount: [u64] Number of tokens to mint
+    /// - max_total_cost: [u64] Most lamports the buyer accepts paying, fails with `PriceExceedsLimit` above it
     /// 
     /// # Returns
     /// - `Result<()>` - Success or error
-    pub fn mint_tokens(ctx: Context<MintTokens>, mint_amount: u64) -> Result<()> {
-        mint_tokens::handler(ctx, mint_amount)
+    pub fn mint_tokens(ctx: Context<MintTokens>, mint_amount: u64, max_total_cost: u64) -> Result<()> {
+        mint_tokens::handler(ctx, mint_amount, max_total_cost)
     }
 
     /// Update NSD minting configuration
     /// 
     /// This instruction updates the configuration parameters for NSD token minting.
-    /// It allows updating maximum supply, mint price, and active status.
+    /// It allows updating maximum supply, mint price, active status and per-wallet limits.
+    /// The maximum supply cannot go below the total minted or, once frozen, above its current value,
+    /// and the mint price must stay within the price band set at initialization.
     /// 
     /// # Accounts
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
@@ -86,37 +111,572 @@ pub mod nsd_minting {
     /// 2. `[signer]` admin: [AccountInfo] Admin authority account
     /// 
     /// # Data
-    /// - max_supply: [Option<u64>] New maximum supply
-    /// - mint_price: [Option<u64>] New mint price
-    /// - is_active: [Option<bool>] New active status
+    /// - args: [UpdateConfigArgs] Configuration fields to update
+    ///   - max_supply: [Option<u64>] New maximum supply
+    ///   - mint_price: [Option<u64>] New mint price
+    ///   - is_active: [Option<bool>] New active status
+    ///   - max_per_wallet: [Option<u64>] New per-wallet mint cap, `Some(0)` removes the cap
+    ///   - max_per_transaction: [Option<u64>] New per-transaction mint limit, `Some(0)` removes the limit
+    ///   - cooldown_seconds: [Option<u64>] New cooldown between mints from one wallet
+    ///   - allowlist_root: [Option<[u8; 32]>] New presale allowlist root, all zeroes closes the presale
     /// 
     /// # Returns
     /// - `Result<()>` - Success or error
-    pub fn update_config(ctx: Context<UpdateConfig>, max_supply: Option<u64>, mint_price: Option<u64>, is_active: Option<bool>) -> Result<()> {
-        update_config::handler(ctx, max_supply, mint_price, is_active)
+    pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+        update_config::handler(ctx, args)
     }
 
     /// Set metadata for NSD tokens
     /// 
     /// This instruction sets the metadata for NSD tokens including name, symbol, and URI.
-    /// It ensures only the admin can set metadata.
+    /// It ensures only the admin can set metadata, and creates the metadata account with the matching
+    /// Metaplex metadata account, or for Token-2022 mints writes it into the mint's token-metadata extension only.
     /// 
     /// # Accounts
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
     /// 1. `[writable]` config: [NsdConfig] 
-    /// 2. `[writable]` metadata: [NsdTokenMetadata] 
+    /// 2. `[writable, optional]` metadata: [NsdTokenMetadata] SPL Token mints only
     /// 3. `[signer]` admin: [AccountInfo] Admin authority account
-    /// 4. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 4. `[writable]` mint: [Mint] NSD token mint account
+    /// 5. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 6. `[writable, optional]` metaplex_metadata: [AccountInfo] Metaplex metadata account, SPL Token mints only
+    /// 7. `[optional]` token_metadata_program: [AccountInfo] Metaplex Token Metadata program, SPL Token mints only
+    /// 8. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 9. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 10. `[]` rent: [AccountInfo] Rent sysvar
     /// 
     /// # Data
     /// - token_mint: [Pubkey] Mint address for NSD token
-    /// - name: [String] Name of the token
-    /// - symbol: [String] Symbol of the token
-    /// - uri: [String] URI for token metadata
+    /// - name: [String] Name of the token, at most `MAX_NAME_LENGTH` bytes
+    /// - symbol: [String] Symbol of the token, at most `MAX_SYMBOL_LENGTH` bytes
+    /// - uri: [String] URI for token metadata, at most `MAX_URI_LENGTH` bytes
     /// 
     /// # Returns
     /// - `Result<()>` - Success or error
     pub fn set_token_metadata(ctx: Context<SetTokenMetadata>, token_mint: Pubkey, name: String, symbol: String, uri: String) -> Result<()> {
         set_token_metadata::handler(ctx, token_mint, name, symbol, uri)
     }
-}
\ No newline at end of file
+
+    /// Withdraw lamports from the treasury
+    /// 
+    /// This instruction moves collected mint proceeds out of the treasury.
+    /// It ensures only the admin can withdraw and never drops the treasury below rent-exemption.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury holding the mint proceeds
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[writable]` destination: [AccountInfo] Account receiving the lamports
+    /// 
+    /// # Data
+    /// - amount: [u64] Number of lamports to withdraw
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        withdraw_treasury::handler(ctx, amount)
+    }
+
+    /// Bind the program's mint authority PDA
+    /// 
+    /// This instruction verifies that the token mint's authority is the program's
+    /// mint authority PDA and records it, enabling minting.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 3. `[]` token_mint: [Mint] NSD token mint account
+    /// 4. `[]` mint_authority: [AccountInfo] Program PDA expected to hold the mint authority
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn bind_mint_authority(ctx: Context<BindMintAuthority>) -> Result<()> {
+        bind_mint_authority::handler(ctx)
+    }
+
+    /// Initialize a sale and create its NSD token mint
+    /// 
+    /// This instruction creates the SPL token mint inside the program with the mint and freeze
+    /// authorities set to program PDAs, then initializes the configuration for it.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury collecting mint proceeds
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[writable, signer]` token_mint: [AccountInfo] New keypair for the NSD token mint
+    /// 5. `[]` mint_authority: [AccountInfo] Program PDA set as the mint authority
+    /// 6. `[]` freeze_authority: [AccountInfo] Program PDA set as the freeze authority
+    /// 7. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 9. `[]` rent: [AccountInfo] Rent sysvar
+    /// 
+    /// # Data
+    /// - decimals: [u8] Number of decimals for the NSD token mint
+    /// - max_supply: [u64] Maximum supply of NSD tokens
+    /// - mint_price: [u64] Price per NSD token in lamports
+    /// - min_price: [Option<u64>] Lowest price the admin may set, if bounded
+    /// - max_price: [Option<u64>] Highest price the admin may set, if bounded
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn initialize_sale(ctx: Context<InitializeSale>, decimals: u8, max_supply: u64, mint_price: u64, min_price: Option<u64>, max_price: Option<u64>) -> Result<()> {
+        initialize_sale::handler(ctx, decimals, max_supply, mint_price, min_price, max_price)
+    }
+
+    /// Mint NSD tokens for a user with the validated account layout
+    /// 
+    /// This instruction mints NSD tokens to the user's associated token account for the configured mint.
+    /// Every account is checked against the configuration and the user.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury receiving the mint price
+    /// 3. `[writable]` token_mint: [Mint] The configured NSD token mint
+    /// 4. `[signer]` user: [AccountInfo] User's wallet address
+    /// 5. `[writable]` user_account: [NsdUser] 
+    /// 6. `[writable]` user_token_account: [Account] User's associated token account, created if needed
+    /// 7. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 8. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 9. `[]` associated_token_program: [AccountInfo] Associated Token program
+    /// 10. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 11. `[]` sale_phase: [SalePhase] Optional, the active sale phase once phases are configured
+    /// 
+    /// # Data
+    /// - mint_amount: [u64] Number of tokens to mint
+    /// - max_total_cost: [u64] Most lamports the buyer accepts paying, fails with `PriceExceedsLimit` above it
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn mint_tokens_v2(ctx: Context<MintTokensV2>, mint_amount: u64, max_total_cost: u64) -> Result<()> {
+        mint_tokens_v2::handler(ctx, mint_amount, max_total_cost)
+    }
+
+    /// Mint NSD tokens during the allowlist presale
+    /// 
+    /// This instruction lets an allowlisted wallet mint up to its allocation while an allowlist root is set.
+    /// It uses the same account layout as `mint_tokens_v2`.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury receiving the mint price
+    /// 3. `[writable]` token_mint: [Mint] The configured NSD token mint
+    /// 4. `[signer]` user: [AccountInfo] Allowlisted wallet address
+    /// 5. `[writable]` user_account: [NsdUser] 
+    /// 6. `[writable]` user_token_account: [Account] User's associated token account, created if needed
+    /// 7. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 8. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 9. `[]` associated_token_program: [AccountInfo] Associated Token program
+    /// 10. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 11. `[]` sale_phase: [SalePhase] Optional, the active sale phase once phases are configured
+    /// 
+    /// # Data
+    /// - mint_amount: [u64] Number of tokens to mint
+    /// - max_total_cost: [u64] Most lamports the buyer accepts paying, fails with `PriceExceedsLimit` above it
+    /// - allocation: [u64] The user's presale allocation from the allowlist leaf
+    /// - proof: [Vec<[u8; 32]>] Merkle proof for the user's leaf
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn presale_mint(ctx: Context<MintTokensV2>, mint_amount: u64, max_total_cost: u64, allocation: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        presale_mint::handler(ctx, mint_amount, max_total_cost, allocation, proof)
+    }
+
+    /// Create a time-boxed sale phase
+    /// 
+    /// This instruction creates a sale phase with its own window, price, wallet cap and optional allowlist.
+    /// Once any phase exists, mints are only accepted inside an active phase window. Phase windows
+    /// may not overlap, so the existing phases are passed to check against.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` sale_phase: [SalePhase] 
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 5. `[]` remaining accounts: [SalePhase] Every existing phase of the configuration
+    /// 
+    /// # Data
+    /// - phase_id: [u8] Identifier of the phase within the configuration
+    /// - start_timestamp: [i64] Unix timestamp at which the phase opens
+    /// - end_timestamp: [i64] Unix timestamp at which the phase closes
+    /// - price: [u64] Price per NSD token in lamports during the phase
+    /// - max_per_wallet: [Option<u64>] Per-wallet mint cap during the phase
+    /// - allowlist_root: [Option<[u8; 32]>] Allowlist root restricting the phase to presale mints
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn create_sale_phase(ctx: Context<CreateSalePhase>, phase_id: u8, start_timestamp: i64, end_timestamp: i64, price: u64, max_per_wallet: Option<u64>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        create_sale_phase::handler(ctx, phase_id, start_timestamp, end_timestamp, price, max_per_wallet, allowlist_root)
+    }
+
+    /// Update a sale phase
+    /// 
+    /// This instruction updates the window, price and limits of an existing sale phase.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [NsdConfig] 
+    /// 2. `[writable]` sale_phase: [SalePhase] 
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[]` remaining accounts: [SalePhase] Every other phase of the configuration, when the window changes
+    /// 
+    /// # Data
+    /// - start_timestamp: [Option<i64>] New opening timestamp
+    /// - end_timestamp: [Option<i64>] New closing timestamp
+    /// - price: [Option<u64>] New price per token in lamports
+    /// - max_per_wallet: [Option<u64>] New per-wallet cap, `Some(0)` removes the cap
+    /// - allowlist_root: [Option<[u8; 32]>] New allowlist root, all zeroes removes the allowlist
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn update_sale_phase(ctx: Context<UpdateSalePhase>, start_timestamp: Option<i64>, end_timestamp: Option<i64>, price: Option<u64>, max_per_wallet: Option<u64>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        update_sale_phase::handler(ctx, start_timestamp, end_timestamp, price, max_per_wallet, allowlist_root)
+    }
+
+    /// Close a sale phase
+    /// 
+    /// This instruction closes a sale phase and returns its rent to the fee payer.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` sale_phase: [SalePhase] 
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn close_sale_phase(ctx: Context<CloseSalePhase>) -> Result<()> {
+        close_sale_phase::handler(ctx)
+    }
+
+    /// Propose a new admin
+    /// 
+    /// This instruction records a pending admin. The current admin stays in charge
+    /// until the pending admin accepts the handover.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` admin: [AccountInfo] Current admin authority account
+    /// 
+    /// # Data
+    /// - new_admin: [Pubkey] The proposed admin authority
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        propose_admin::handler(ctx, new_admin)
+    }
+
+    /// Accept the admin handover
+    /// 
+    /// This instruction makes the pending admin the admin of the configuration.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` new_admin: [AccountInfo] Pending admin authority account
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        accept_admin::handler(ctx)
+    }
+
+    /// Cancel the admin handover
+    /// 
+    /// This instruction withdraws a pending admin proposal.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` admin: [AccountInfo] Current admin authority account
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        cancel_admin_transfer::handler(ctx)
+    }
+
+    /// Update metadata for NSD tokens
+    /// 
+    /// This instruction updates the name, symbol and URI of the token metadata, leaving fields
+    /// passed as `None` unchanged. For SPL Token mints the metadata account is reallocated to fit the new
+    /// values and the Metaplex metadata account is updated to match; Token-2022 mints update their metadata extension.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [NsdConfig] 
+    /// 2. `[writable, optional]` metadata: [NsdTokenMetadata] SPL Token mints only
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 5. `[writable]` mint: [Mint] NSD token mint account
+    /// 6. `[writable, optional]` metaplex_metadata: [AccountInfo] Metaplex metadata account, SPL Token mints only
+    /// 7. `[optional]` token_metadata_program: [AccountInfo] Metaplex Token Metadata program, SPL Token mints only
+    /// 8. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 9. `[]` system_program: [AccountInfo] Auto-generated, for account reallocation
+    /// 
+    /// # Data
+    /// - name: [Option<String>] New name of the token, at most `MAX_NAME_LENGTH` bytes
+    /// - symbol: [Option<String>] New symbol of the token, at most `MAX_SYMBOL_LENGTH` bytes
+    /// - uri: [Option<String>] New URI for token metadata, at most `MAX_URI_LENGTH` bytes
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn update_token_metadata(ctx: Context<UpdateTokenMetadata>, name: Option<String>, symbol: Option<String>, uri: Option<String>) -> Result<()> {
+        update_token_metadata::handler(ctx, name, symbol, uri)
+    }
+
+    /// Lock metadata for NSD tokens
+    /// 
+    /// This instruction makes the token metadata permanently immutable, including the Metaplex metadata
+    /// or the Token-2022 metadata extension.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [NsdConfig] 
+    /// 2. `[writable, optional]` metadata: [NsdTokenMetadata] SPL Token mints only
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 5. `[writable]` mint: [Mint] NSD token mint account
+    /// 6. `[writable, optional]` metaplex_metadata: [AccountInfo] Metaplex metadata account, SPL Token mints only
+    /// 7. `[optional]` token_metadata_program: [AccountInfo] Metaplex Token Metadata program, SPL Token mints only
+    /// 8. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn lock_metadata(ctx: Context<LockMetadata>) -> Result<()> {
+        lock_metadata::handler(ctx)
+    }
+
+    /// Freeze the maximum supply
+    /// 
+    /// This instruction permanently stops any further increase of the maximum supply.
+    /// The supply can still be lowered down to the total minted.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn freeze_max_supply(ctx: Context<FreezeMaxSupply>) -> Result<()> {
+        freeze_max_supply::handler(ctx)
+    }
+
+    /// Finalize a sale
+    /// 
+    /// This instruction sets the token mint's mint authority to `None` and marks the configuration
+    /// finalized, after which every mint and `update_config` fail. Supplying a destination sweeps
+    /// the treasury balance above its rent-exempt minimum and the bonding-curve reserve.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury holding the mint proceeds
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[writable]` token_mint: [Mint] NSD token mint account
+    /// 5. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 6. `[writable, optional]` destination: [AccountInfo] Account receiving the swept treasury
+    /// 7. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn finalize_sale(ctx: Context<FinalizeSale>) -> Result<()> {
+        finalize_sale::handler(ctx)
+    }
+
+    /// Set the price tiers
+    /// 
+    /// This instruction replaces the table of prices by cumulative supply sold. A mint crossing
+    /// tier boundaries is charged per tier; an empty list restores the flat `mint_price`.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 
+    /// # Data
+    /// - tiers: [Vec<PriceTier>] Price tiers ordered by start supply, the first starting at zero
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn set_price_tiers(ctx: Context<SetPriceTiers>, tiers: Vec<PriceTier>) -> Result<()> {
+        set_price_tiers::handler(ctx, tiers)
+    }
+
+    /// Set the bonding curve
+    /// 
+    /// This instruction sets or clears the linear bonding curve that prices every mint by `total_minted`
+    /// and lets holders sell tokens back to the treasury along the same curve. The curve can only
+    /// change before the first token is minted, and only on a mint whose whole supply came from the sale.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[]` token_mint: [Mint] NSD token mint account
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 
+    /// # Data
+    /// - curve: [Option<BondingCurve>] Base price and slope of the curve, `None` to clear it
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn set_bonding_curve(ctx: Context<SetBondingCurve>, curve: Option<BondingCurve>) -> Result<()> {
+        set_bonding_curve::handler(ctx, curve)
+    }
+
+    /// Sell NSD tokens
+    /// 
+    /// This instruction burns the user's tokens and refunds the integral of the bonding curve
+    /// over the returned supply from the treasury. Selling stays open after the sale is finalized.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` user: [AccountInfo] Seller receiving the refund
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[writable]` treasury: [NsdTreasury] Treasury paying the refund
+    /// 3. `[writable]` token_mint: [Mint] NSD token mint account
+    /// 4. `[writable]` user_token_account: [TokenAccount] User's associated token account
+    /// 5. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 
+    /// # Data
+    /// - sell_amount: [u64] Number of tokens to sell
+    /// - min_lamports_out: [u64] Smallest refund accepted
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn sell_tokens(ctx: Context<SellTokens>, sell_amount: u64, min_lamports_out: u64) -> Result<()> {
+        sell_tokens::handler(ctx, sell_amount, min_lamports_out)
+    }
+
+    /// Set the Dutch auction
+    /// 
+    /// This instruction sets or clears the Dutch auction, whose price decays linearly from a start price
+    /// to a floor over a configured window and prices every mint at the current time.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 
+    /// # Data
+    /// - auction: [Option<DutchAuction>] Start price, floor price, start timestamp and decay interval, `None` to clear it
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn set_dutch_auction(ctx: Context<SetDutchAuction>, auction: Option<DutchAuction>) -> Result<()> {
+        set_dutch_auction::handler(ctx, auction)
+    }
+
+    /// Read the current price
+    /// 
+    /// This view instruction returns the price of the next token in lamports as return data.
+    /// 
+    /// # Accounts
+    /// 0. `[]` config: [NsdConfig] 
+    /// 1. `[optional]` sale_phase: [SalePhase] Active sale phase, required once phases are configured
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Price of the next token in lamports
+    pub fn current_price(ctx: Context<CurrentPrice>) -> Result<u64> {
+        current_price::handler(ctx)
+    }
+
+    /// Set the accepted payment mints
+    /// 
+    /// This instruction replaces the SPL tokens accepted by `mint_tokens_with_token`, each with its own price.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 
+    /// # Data
+    /// - payment_mints: [Vec<PaymentMint>] Accepted payment mints and their prices per token, empty for lamports only
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn set_payment_mints(ctx: Context<SetPaymentMints>, payment_mints: Vec<PaymentMint>) -> Result<()> {
+        set_payment_mints::handler(ctx, payment_mints)
+    }
+
+    /// Mint NSD tokens for a user against an SPL token payment
+    /// 
+    /// This instruction mints like `mint_tokens_v2`, collecting the price in an accepted payment mint
+    /// into the treasury's associated token account for that mint instead of lamports.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [NsdConfig] 
+    /// 2. `[]` treasury: [NsdTreasury] Treasury owning the payment token account
+    /// 3. `[writable]` token_mint: [Mint] The configured NSD token mint
+    /// 4. `[signer]` user: [AccountInfo] User's wallet address, owner of the payment token account
+    /// 5. `[writable]` user_account: [NsdUser] 
+    /// 6. `[writable]` user_token_account: [Account] User's associated token account, created if needed
+    /// 7. `[]` mint_authority: [AccountInfo] Program PDA holding the mint authority
+    /// 8. `[]` token_program: [AccountInfo] Token program, SPL Token or Token-2022
+    /// 9. `[]` associated_token_program: [AccountInfo] Associated Token program
+    /// 10. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 11. `[]` payment_mint: [Mint] The accepted payment token
+    /// 12. `[writable]` user_payment_account: [Account] User's token account for the payment mint
+    /// 13. `[writable]` treasury_payment_account: [Account] Treasury's associated token account for the payment mint, created if needed
+    /// 14. `[]` payment_token_program: [AccountInfo] Token program owning the payment mint
+    /// 15. `[]` sale_phase: [SalePhase] Optional, the active sale phase once phases are configured
+    /// 
+    /// # Data
+    /// - mint_amount: [u64] Number of tokens to mint
+    /// - max_total_cost: [u64] Most payment tokens the buyer accepts paying, fails with `PriceExceedsLimit` above it
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn mint_tokens_with_token(ctx: Context<MintTokensWithToken>, mint_amount: u64, max_total_cost: u64) -> Result<()> {
+        mint_tokens_with_token::handler(ctx, mint_amount, max_total_cost)
+    }
+
+    /// Withdraw payment tokens from the treasury
+    /// 
+    /// This instruction moves SPL tokens collected by `mint_tokens_with_token` out of the treasury's token account.
+    /// 
+    /// # Accounts
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [NsdConfig] 
+    /// 2. `[]` treasury: [NsdTreasury] Treasury owning the payment token account
+    /// 3. `[signer]` admin: [AccountInfo] Admin authority account
+    /// 4. `[]` payment_mint: [Mint] The payment token to withdraw
+    /// 5. `[writable]` treasury_payment_account: [Account] Treasury's associated token account for the payment mint
+    /// 6. `[writable]` destination: [Account] Token account receiving the tokens
+    /// 7. `[]` payment_token_program: [AccountInfo] Token program owning the payment mint
+    /// 
+    /// # Data
+    /// - amount: [u64] Number of tokens to withdraw, in base units
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn withdraw_payment_tokens(ctx: Context<WithdrawPaymentTokens>, amount: u64) -> Result<()> {
+        withdraw_payment_tokens::handler(ctx, amount)
+    }
+
+    /// Quote the cost of a mint
+    /// 
+    /// This view instruction returns the lamports a mint of `mint_amount` tokens would charge as return data,
+    /// priced exactly like the mint instructions.
+    /// 
+    /// # Accounts
+    /// 0. `[]` config: [NsdConfig] 
+    /// 1. `[optional]` sale_phase: [SalePhase] Active sale phase, required once phases are configured
+    /// 
+    /// # Data
+    /// - mint_amount: [u64] Number of tokens to quote
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Total cost of the mint in lamports
+    pub fn quote_mint(ctx: Context<CurrentPrice>, mint_amount: u64) -> Result<u64> {
+        quote_mint::handler(ctx, mint_amount)
+    }
+}
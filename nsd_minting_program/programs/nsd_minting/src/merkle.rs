@@ -0,0 +1,50 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Compute the allowlist leaf for a wallet and its presale allocation
+/// 
+/// The leaf is `sha256(user || allocation)` with the allocation encoded as little-endian u64.
+/// Off-chain tooling must build the tree from the same leaves.
+/// 
+/// # Parameters
+/// - `user` - The allowlisted wallet
+/// - `allocation` - Maximum number of tokens the wallet may mint in the presale
+/// 
+/// # Returns
+/// - `[u8; 32]` - The leaf hash
+pub fn allowlist_leaf(user: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[user.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+/// Verify a Merkle proof against a root
+/// 
+/// Each pair of nodes is hashed in sorted order, so proofs don't need to carry
+/// left/right position flags.
+/// 
+/// # Parameters
+/// - `root` - The Merkle root stored on-chain
+/// - `leaf` - The leaf being proven
+/// - `proof` - Sibling hashes from the leaf up to the root
+/// 
+/// # Returns
+/// - `bool` - Whether the proof is valid
+pub fn verify_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
@@ -0,0 +1,62 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::Metadata;
+
+/// CPI to the Token Metadata program to update the Metaplex metadata account of an SPL Token mint
+/// 
+/// Shared by `update_token_metadata` and `lock_metadata`. The values are copied from the
+/// program's metadata account, and the mint authority PDA signs as update authority.
+/// 
+/// # Parameters
+/// - `config` - The configuration account of the sale
+/// - `mint_authority` - The program PDA holding the mint authority
+/// - `metadata` - The program's metadata account, if passed
+/// - `metaplex_metadata` - The Metaplex metadata account, if passed
+/// - `token_metadata_program` - The Metaplex Token Metadata program, if passed
+/// - `is_mutable` - Whether the Metaplex metadata stays mutable
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn update_metadata<'info>(
+    config: &Account<'info, NsdConfig>,
+    mint_authority: &AccountInfo<'info>,
+    metadata: Option<&Account<'info, NsdTokenMetadata>>,
+    metaplex_metadata: Option<&UncheckedAccount<'info>>,
+    token_metadata_program: Option<&Program<'info, Metadata>>,
+    is_mutable: bool,
+) -> Result<()> {
+    let (Some(metadata), Some(metaplex_metadata), Some(token_metadata_program)) =
+        (metadata, metaplex_metadata, token_metadata_program)
+    else {
+        return err!(NsdError::MissingMetadataAccounts);
+    };
+    let config_key = config.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        MINT_AUTHORITY_SEED,
+        config_key.as_ref(),
+        &[config.mint_authority_bump],
+    ]];
+    anchor_spl::metadata::update_metadata_accounts_v2(
+        CpiContext::new_with_signer(token_metadata_program.to_account_info(),
+            anchor_spl::metadata::UpdateMetadataAccountsV2 {
+                metadata: metaplex_metadata.to_account_info(),
+                update_authority: mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        None,
+        Some(metadata.metaplex_data()),
+        None,
+        Some(is_mutable),
+    )
+}
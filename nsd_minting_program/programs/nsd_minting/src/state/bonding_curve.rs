@@ -0,0 +1,69 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+
+// Import required modules and macros
+use crate::error::NsdError;
+use anchor_lang::prelude::*;
+
+/// Linear bonding curve structure
+/// 
+/// Prices token number `n` of the cumulative supply (counting from zero) at
+/// `base_price + slope * n` lamports. Buying walks up the curve and selling walks back down,
+/// so the lamports paid for a range of the supply are exactly the lamports refunded for it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BondingCurve {
+    /// Price in lamports of the first token
+    pub base_price: u64,
+    
+    /// Price increase in lamports per token sold
+    pub slope: u64,
+}
+
+/// Implementation of the curve arithmetic for BondingCurve
+impl BondingCurve {
+    /// Price of the token at a position in the supply
+    /// 
+    /// # Parameters
+    /// - `supply` - Number of tokens sold before this one
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Price of the token in lamports
+    pub fn price_at(&self, supply: u64) -> Result<u64> {
+        self.slope
+            .checked_mul(supply)
+            .and_then(|increase| increase.checked_add(self.base_price))
+            .ok_or_else(|| NsdError::ArithmeticOverflow.into())
+    }
+    
+    /// Total price of a range of the supply
+    /// 
+    /// This is the integral of the curve over the range: the sum of the prices of tokens
+    /// `start_supply` to `start_supply + amount - 1`, computed in closed form.
+    /// 
+    /// # Parameters
+    /// - `start_supply` - Number of tokens sold before the range
+    /// - `amount` - Number of tokens in the range
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Total price of the range in lamports
+    pub fn cost(&self, start_supply: u64, amount: u64) -> Result<u64> {
+        // amount * base_price + slope * (amount * start_supply + amount * (amount - 1) / 2)
+        let amount = u128::from(amount);
+        let steps = amount
+            .checked_mul(u128::from(start_supply))
+            .and_then(|steps| steps.checked_add(amount * amount.saturating_sub(1) / 2))
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        let total = steps
+            .checked_mul(u128::from(self.slope))
+            .and_then(|increase| increase.checked_add(amount * u128::from(self.base_price)))
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        u64::try_from(total).map_err(|_| NsdError::ArithmeticOverflow.into())
+    }
+}
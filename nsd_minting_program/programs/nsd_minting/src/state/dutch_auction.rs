@@ -0,0 +1,64 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+
+// Import required modules and macros
+use crate::error::NsdError;
+use anchor_lang::prelude::*;
+
+/// Dutch auction structure
+/// 
+/// Prices every token at `start_price` until `start_timestamp`, then lowers the price linearly
+/// over `decay_interval` seconds down to `floor_price`, where it stays.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DutchAuction {
+    /// Price per token in lamports when the auction starts
+    pub start_price: u64,
+    
+    /// Lowest price per token in lamports, reached at the end of the decay
+    pub floor_price: u64,
+    
+    /// Unix timestamp at which the price starts to decay
+    pub start_timestamp: i64,
+    
+    /// Number of seconds over which the price falls from the start price to the floor
+    pub decay_interval: u64,
+}
+
+/// Implementation of the price decay for DutchAuction
+impl DutchAuction {
+    /// Price per token at a point in time
+    /// 
+    /// The decay is rounded down, so the price never falls below the exact linear value.
+    /// 
+    /// # Parameters
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Price per token in lamports
+    pub fn price_at(&self, now: i64) -> Result<u64> {
+        // Full price until the auction starts
+        let Ok(elapsed) = u64::try_from(now.saturating_sub(self.start_timestamp)) else {
+            return Ok(self.start_price);
+        };
+        
+        // Floor price once the decay is over
+        if elapsed >= self.decay_interval {
+            return Ok(self.floor_price);
+        }
+        
+        // Linear decay in between
+        let range = self.start_price
+            .checked_sub(self.floor_price)
+            .ok_or(NsdError::InvalidDutchAuction)?;
+        let decay = u128::from(range) * u128::from(elapsed) / u128::from(self.decay_interval);
+        // decay < range, so this fits and never underflows
+        Ok(self.start_price - decay as u64)
+    }
+}
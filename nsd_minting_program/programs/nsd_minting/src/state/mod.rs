@@ -5,8 +5,20 @@ pub mod nsd_config;
 pub mod nsd_token;
 pub mod nsd_user;
 pub mod nsd_token_metadata;
+pub mod nsd_treasury;
+pub mod sale_phase;
+pub mod price_tier;
+pub mod bonding_curve;
+pub mod dutch_auction;
+pub mod payment_mint;
 
 pub use nsd_config::*;
 pub use nsd_token::*;
 pub use nsd_user::*;
 pub use nsd_token_metadata::*;
+pub use nsd_treasury::*;
+pub use sale_phase::*;
+pub use price_tier::*;
+pub use bonding_curve::*;
+pub use dutch_auction::*;
+pub use payment_mint::*;
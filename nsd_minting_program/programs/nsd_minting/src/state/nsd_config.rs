@@ -8,6 +8,10 @@
 //! account management, error handling, and security.
 
 // Import required modules and macros
+use crate::constants::{MAX_PAYMENT_MINTS, MAX_PRICE_TIERS};
+use crate::error::NsdError;
+use crate::events::ConfigSettings;
+use crate::state::{BondingCurve, DutchAuction, NsdUser, PaymentMint, PriceTier, SalePhase};
 use anchor_lang::prelude::*;
 
 /// Configuration structure for NSD minting
@@ -36,4 +40,618 @@ pub struct NsdConfig {
     
     /// Bump seed for the configuration account
     pub bump: u8,
-}
\ No newline at end of file
+    
+    /// Total lamports collected into the treasury from mints
+    pub total_revenue: u64,
+    
+    /// Total lamports withdrawn from the treasury by the admin
+    pub total_withdrawn: u64,
+    
+    /// Bump seed for the treasury account
+    pub treasury_bump: u8,
+    
+    /// Whether the token mint's authority has been verified as the program PDA
+    pub mint_authority_bound: bool,
+    
+    /// Bump seed for the mint authority PDA
+    pub mint_authority_bump: u8,
+    
+    /// Number of decimals of the token mint
+    pub decimals: u8,
+    
+    /// Bump seed for the freeze authority PDA
+    pub freeze_authority_bump: u8,
+    
+    /// Maximum number of tokens a single wallet may mint, if capped
+    pub max_per_wallet: Option<u64>,
+    
+    /// Maximum number of tokens per mint transaction
+    pub max_per_transaction: u64,
+    
+    /// Minimum number of seconds between two mints from the same wallet
+    pub cooldown_seconds: u64,
+    
+    /// Merkle root of the presale allowlist, the presale is open while set
+    pub allowlist_root: Option<[u8; 32]>,
+    
+    /// Number of sale phases currently configured
+    pub phase_count: u8,
+    
+    /// Admin proposed by the current admin, who must accept before taking over
+    pub pending_admin: Option<Pubkey>,
+    
+    /// Lowest price per token the admin may set, if bounded
+    pub min_price: Option<u64>,
+    
+    /// Highest price per token the admin may set, if bounded
+    pub max_price: Option<u64>,
+    
+    /// Whether the maximum supply is frozen; once set it can never be raised or unset
+    pub max_supply_frozen: bool,
+    
+    /// Whether the sale has been finalized; the mint authority is revoked and the config is read-only
+    pub is_finalized: bool,
+    
+    /// Price tiers by cumulative supply sold, replacing `mint_price` while not empty
+    pub price_tiers: Vec<PriceTier>,
+    
+    /// Bonding curve pricing every mint and sell by `total_minted`, if set
+    pub bonding_curve: Option<BondingCurve>,
+    
+    /// Total lamports refunded from the treasury for tokens sold back along the bonding curve
+    pub total_refunded: u64,
+    
+    /// Dutch auction pricing every mint by the current time, if set
+    pub dutch_auction: Option<DutchAuction>,
+    
+    /// SPL tokens accepted as payment by `mint_tokens_with_token`, each with its own price
+    pub payment_mints: Vec<PaymentMint>,
+}
+
+/// Implementation of minting rules for NsdConfig
+/// 
+/// These methods are shared by every mint instruction so the sale rules
+/// are enforced identically whichever account layout is used.
+impl NsdConfig {
+    /// Set up a new configuration and check it
+    /// 
+    /// Shared by `initialize_config` and `initialize_sale`. Every field starts at its
+    /// default; the bumps and the mint authority state are left unbound for the caller
+    /// to record, since they depend on how the mint was created.
+    /// 
+    /// # Parameters
+    /// - `admin` - The admin authority
+    /// - `token_mint` - The mint address for NSD token
+    /// - `max_supply` - Maximum supply of NSD tokens
+    /// - `mint_price` - Price per NSD token in lamports
+    /// - `min_price` - Lowest price per token the admin may set, if bounded
+    /// - `max_price` - Highest price per token the admin may set, if bounded
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn init(
+        &mut self,
+        admin: Pubkey,
+        token_mint: Pubkey,
+        max_supply: u64,
+        mint_price: u64,
+        min_price: Option<u64>,
+        max_price: Option<u64>,
+    ) -> Result<()> {
+        // Store the admin authority
+        self.admin = admin;
+        // Store the token mint address
+        self.token_mint = token_mint;
+        // Set the maximum supply
+        self.max_supply = max_supply;
+        // Set the mint price per token
+        self.mint_price = mint_price;
+        // Initialize total minted to zero
+        self.total_minted = 0;
+        // Set minting to active by default
+        self.is_active = true;
+        // Initialize revenue accounting to zero
+        self.total_revenue = 0;
+        self.total_withdrawn = 0;
+        // The bumps and the mint authority are recorded by the caller
+        self.bump = 0;
+        self.treasury_bump = 0;
+        self.mint_authority_bound = false;
+        self.mint_authority_bump = 0;
+        self.decimals = 0;
+        self.freeze_authority_bump = 0;
+        // Start without per-wallet limits
+        self.max_per_wallet = None;
+        self.max_per_transaction = u64::MAX;
+        self.cooldown_seconds = 0;
+        // Start with the presale closed
+        self.allowlist_root = None;
+        // Start without sale phases
+        self.phase_count = 0;
+        // Start without a pending admin handover
+        self.pending_admin = None;
+        // Store the price band
+        self.min_price = min_price;
+        self.max_price = max_price;
+        // The maximum supply starts adjustable
+        self.max_supply_frozen = false;
+        // The sale starts open
+        self.is_finalized = false;
+        self.price_tiers = Vec::new();
+        self.bonding_curve = None;
+        self.total_refunded = 0;
+        self.dutch_auction = None;
+        self.payment_mints = Vec::new();
+        
+        // Check the initial configuration
+        self.validate()
+    }
+    
+    /// Resolve the sale phase that applies to a mint
+    /// 
+    /// Without any configured phases no phase applies. Once phases exist, the supplied
+    /// phase must belong to this configuration and be active at `now`. Phase windows never
+    /// overlap, so at most one phase can be active and the buyer has no choice of phase.
+    /// 
+    /// # Parameters
+    /// - `phase` - The sale phase supplied with the mint, if any
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `Result<Option<&SalePhase>>` - The active phase, if phases are configured
+    pub fn active_phase<'a>(&self, phase: Option<&'a SalePhase>, now: i64) -> Result<Option<&'a SalePhase>> {
+        // Phases are optional, sales without phases are gated by is_active only
+        if self.phase_count == 0 {
+            return Ok(None);
+        }
+        
+        // Check the supplied phase is open right now
+        let phase = phase.ok_or(NsdError::NoActiveSalePhase)?;
+        require!(phase.is_active_at(now), NsdError::NoActiveSalePhase);
+        Ok(Some(phase))
+    }
+    
+    /// Check a sale phase window against every other phase of this configuration
+    /// 
+    /// `other_phases` must hold each other live phase of the configuration exactly once, so
+    /// `expected` of them: `phase_count` when creating a phase and one less when updating one.
+    /// Windows are half-open, so one phase may start exactly when another ends.
+    /// 
+    /// # Parameters
+    /// - `config` - Address of this configuration
+    /// - `phase` - Address of the phase being created or updated, never among the others
+    /// - `start_timestamp` - Opening timestamp of the phase
+    /// - `end_timestamp` - Closing timestamp of the phase
+    /// - `other_phases` - The other sale phase accounts, passed as remaining accounts
+    /// - `expected` - Number of other phases the configuration has
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn require_phase_window_free(
+        &self,
+        config: &Pubkey,
+        phase: &Pubkey,
+        start_timestamp: i64,
+        end_timestamp: i64,
+        other_phases: &[AccountInfo],
+        expected: usize,
+    ) -> Result<()> {
+        // Check every other phase is passed, each once
+        require!(other_phases.len() == expected, NsdError::InvalidPhaseAccounts);
+        for (index, info) in other_phases.iter().enumerate() {
+            require!(
+                info.key != phase && other_phases[..index].iter().all(|other| other.key != info.key),
+                NsdError::InvalidPhaseAccounts
+            );
+            
+            // Check it is a live phase of this configuration
+            // Closed phases are no longer owned by the program
+            require!(info.owner == &crate::ID, NsdError::InvalidPhaseAccounts);
+            let other = SalePhase::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(other.config == *config, NsdError::InvalidPhaseAccounts);
+            
+            // Check the windows do not overlap
+            require!(
+                end_timestamp <= other.start_timestamp || other.end_timestamp <= start_timestamp,
+                NsdError::OverlappingSalePhases
+            );
+        }
+        Ok(())
+    }
+    
+    /// Validate a public mint request against the configuration
+    /// 
+    /// Checks the public mint rules with `validate_public_mint`, then prices the mint in lamports.
+    /// 
+    /// # Parameters
+    /// - `user` - The minting user's history account
+    /// - `phase` - The sale phase supplied with the mint, if any
+    /// - `mint_amount` - Number of tokens to mint
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Total cost of the mint in lamports
+    pub fn validate_mint(&self, user: &NsdUser, phase: Option<&SalePhase>, mint_amount: u64, now: i64) -> Result<u64> {
+        let phase = self.validate_public_mint(user, phase, mint_amount, now)?;
+        
+        // Calculate the total cost of minting
+        self.quote(phase, mint_amount, now)
+    }
+    
+    /// Check a public mint request against the configuration, without pricing it
+    /// 
+    /// Checks that public minting is active and not restricted to an allowlist,
+    /// then applies the shared mint limits. Token-paid mints use this directly,
+    /// since their price comes from the payment mint rather than the lamport pricing.
+    /// 
+    /// # Parameters
+    /// - `user` - The minting user's history account
+    /// - `phase` - The sale phase supplied with the mint, if any
+    /// - `mint_amount` - Number of tokens to mint
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `Result<Option<&SalePhase>>` - The active phase, if phases are configured
+    pub fn validate_public_mint<'a>(
+        &self,
+        user: &NsdUser,
+        phase: Option<&'a SalePhase>,
+        mint_amount: u64,
+        now: i64,
+    ) -> Result<Option<&'a SalePhase>> {
+        // Check the sale has not been finalized
+        self.require_not_finalized()?;
+        
+        // Check if minting is active
+        require!(self.is_active, NsdError::MintingNotActive);
+        
+        // Check the sale phase window
+        // Allowlisted phases only accept presale mints
+        let phase = self.active_phase(phase, now)?;
+        require!(
+            phase.is_none_or(|p| p.allowlist_root.is_none()),
+            NsdError::AllowlistRequired
+        );
+        
+        self.validate_mint_limits(user, phase, mint_amount, now)?;
+        Ok(phase)
+    }
+    
+    /// Validate a mint request against the limits shared by every mint path
+    /// 
+    /// Checks that the mint authority is bound to the program, the maximum supply
+    /// is respected and the user's per-wallet limits are respected.
+    /// The active sale phase, if any, may add its own wallet cap.
+    /// Pricing is left to the caller, since each payment path prices the mint differently.
+    /// 
+    /// # Parameters
+    /// - `user` - The minting user's history account
+    /// - `phase` - The active sale phase, if any
+    /// - `mint_amount` - Number of tokens to mint
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success if the mint is within every limit
+    pub fn validate_mint_limits(&self, user: &NsdUser, phase: Option<&SalePhase>, mint_amount: u64, now: i64) -> Result<()> {
+        // Check the sale has not been finalized
+        self.require_not_finalized()?;
+        
+        // Check the mint authority has been handed to the program
+        // Without it the mint CPI cannot be signed
+        require!(self.mint_authority_bound, NsdError::MintAuthorityNotBound);
+        
+        // Check if we haven't exceeded max supply
+        let new_total = self.total_minted
+            .checked_add(mint_amount)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        require!(new_total <= self.max_supply, NsdError::ExceedsMaxSupply);
+        
+        // Check the per-transaction limit
+        require!(mint_amount <= self.max_per_transaction, NsdError::ExceedsTransactionLimit);
+        
+        // Check the per-wallet cap, if any
+        if let Some(cap) = self.max_per_wallet {
+            let wallet_total = user.tokens_minted
+                .checked_add(mint_amount)
+                .ok_or(NsdError::ArithmeticOverflow)?;
+            require!(wallet_total <= cap, NsdError::ExceedsWalletLimit);
+        }
+        
+        // Check the phase's per-wallet cap, if any
+        if let Some(cap) = phase.and_then(|p| p.max_per_wallet) {
+            let wallet_total = user.tokens_minted
+                .checked_add(mint_amount)
+                .ok_or(NsdError::ArithmeticOverflow)?;
+            require!(wallet_total <= cap, NsdError::ExceedsWalletLimit);
+        }
+        
+        // Check the cooldown since the wallet's last mint
+        let cooldown = i64::try_from(self.cooldown_seconds).map_err(|_| NsdError::ArithmeticOverflow)?;
+        let next_mint_at = user.last_mint_timestamp
+            .checked_add(cooldown)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        require!(now >= next_mint_at, NsdError::MintCooldownActive);
+        
+        Ok(())
+    }
+    
+    /// Calculate the cost of minting at the current supply
+    /// 
+    /// A bonding curve, when set, prices every mint by the integral of the curve over the
+    /// minted range, so sells can walk back down the same curve. A Dutch auction, when set,
+    /// prices every token of the mint at the auction price at `now`. Otherwise the active phase's
+    /// price replaces every other price, then the price tiers apply, each token being charged
+    /// at the tier covering its position in the cumulative supply, so a purchase crossing tier
+    /// boundaries is split between the tiers. Without tiers every token costs the mint price.
+    /// Clients call this on a fetched config to quote the exact amount charged.
+    /// 
+    /// # Parameters
+    /// - `phase` - The active sale phase, if any
+    /// - `mint_amount` - Number of tokens to mint
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Total cost of the mint in lamports
+    pub fn quote(&self, phase: Option<&SalePhase>, mint_amount: u64, now: i64) -> Result<u64> {
+        // The bonding curve sets every price
+        if let Some(curve) = self.bonding_curve {
+            return curve.cost(self.total_minted, mint_amount);
+        }
+        
+        // Flat price from the auction, the phase or the configuration
+        if self.dutch_auction.is_some() || phase.is_some() || self.price_tiers.is_empty() {
+            let unit_price = self.unit_price(phase, now)?;
+            let total_cost = mint_amount
+                .checked_mul(unit_price)
+                .ok_or(NsdError::ArithmeticOverflow)?;
+            return Ok(total_cost);
+        }
+        
+        // Walk the tiers from the current supply to the supply after this mint
+        let mut sold = self.total_minted;
+        let end = sold
+            .checked_add(mint_amount)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        let mut total_cost: u64 = 0;
+        for (index, tier) in self.price_tiers.iter().enumerate() {
+            let tier_end = self.price_tiers.get(index + 1).map_or(u64::MAX, |next| next.start_supply);
+            if sold >= tier_end {
+                continue;
+            }
+            let amount = end.min(tier_end) - sold;
+            let tier_cost = amount
+                .checked_mul(tier.price)
+                .ok_or(NsdError::ArithmeticOverflow)?;
+            total_cost = total_cost
+                .checked_add(tier_cost)
+                .ok_or(NsdError::ArithmeticOverflow)?;
+            sold += amount;
+            if sold == end {
+                break;
+            }
+        }
+        Ok(total_cost)
+    }
+    
+    /// Calculate the cost of a mint paid with an SPL token
+    /// 
+    /// Each payment mint has a flat price; the lamport pricing modes do not apply.
+    /// 
+    /// # Parameters
+    /// - `payment_mint` - Mint of the token paid with
+    /// - `mint_amount` - Number of tokens to mint
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Total cost in base units of the payment token
+    pub fn quote_token(&self, payment_mint: &Pubkey, mint_amount: u64) -> Result<u64> {
+        let payment = self.payment_mints
+            .iter()
+            .find(|payment| payment.mint == *payment_mint)
+            .ok_or(NsdError::PaymentMintNotAccepted)?;
+        mint_amount
+            .checked_mul(payment.price)
+            .ok_or_else(|| NsdError::ArithmeticOverflow.into())
+    }
+    
+    /// Price of the next token
+    /// 
+    /// Follows the same precedence as `quote`: the bonding curve at `total_minted`, the Dutch
+    /// auction at `now`, the active phase's price, the price tier covering `total_minted`,
+    /// then the mint price.
+    /// 
+    /// # Parameters
+    /// - `phase` - The active sale phase, if any
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Price per token in lamports
+    pub fn unit_price(&self, phase: Option<&SalePhase>, now: i64) -> Result<u64> {
+        if let Some(curve) = self.bonding_curve {
+            return curve.price_at(self.total_minted);
+        }
+        if let Some(auction) = self.dutch_auction {
+            return auction.price_at(now);
+        }
+        if let Some(phase) = phase {
+            return Ok(phase.price);
+        }
+        let tier = self.price_tiers.iter().rev().find(|tier| tier.start_supply <= self.total_minted);
+        Ok(tier.map_or(self.mint_price, |tier| tier.price))
+    }
+    
+    /// Calculate the refund for selling tokens back along the bonding curve
+    /// 
+    /// The refund is the integral of the curve over the top `sell_amount` tokens of the supply,
+    /// which is exactly what buying them back would cost.
+    /// 
+    /// # Parameters
+    /// - `sell_amount` - Number of tokens to sell
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Refund in lamports
+    pub fn quote_sell(&self, sell_amount: u64) -> Result<u64> {
+        let curve = self.bonding_curve.ok_or(NsdError::BondingCurveNotSet)?;
+        let start_supply = self.total_minted
+            .checked_sub(sell_amount)
+            .ok_or(NsdError::ExceedsCurveSupply)?;
+        curve.cost(start_supply, sell_amount)
+    }
+    
+    /// Lamports the treasury must hold to buy back the whole supply along the bonding curve
+    /// 
+    /// Withdrawals leave this reserve in the treasury so every holder can always sell.
+    /// 
+    /// # Returns
+    /// - `Result<u64>` - Reserve in lamports, zero without a bonding curve
+    pub fn curve_reserve(&self) -> Result<u64> {
+        match self.bonding_curve {
+            Some(curve) => curve.cost(0, self.total_minted),
+            None => Ok(0),
+        }
+    }
+    
+    /// Check that the sale has not been finalized
+    /// 
+    /// A finalized sale can never mint again and its configuration can no longer change.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn require_not_finalized(&self) -> Result<()> {
+        require!(!self.is_finalized, NsdError::SaleFinalized);
+        Ok(())
+    }
+    
+    /// Check the configuration invariants
+    /// 
+    /// Run after every change to the supply or price settings and after every mint,
+    /// so no combination of updates can leave the sale in an inconsistent state.
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn validate(&self) -> Result<()> {
+        // The supply cap can never fall below what was already minted
+        require!(self.max_supply >= self.total_minted, NsdError::MaxSupplyBelowMinted);
+        
+        // The price band must not be empty
+        if let (Some(min), Some(max)) = (self.min_price, self.max_price) {
+            require!(min <= max, NsdError::InvalidPriceBand);
+        }
+        
+        // The mint price must lie within the band
+        self.require_price_in_band(self.mint_price)?;
+        
+        // The price tiers start at zero, ascend strictly and stay within the band
+        require!(self.price_tiers.len() <= MAX_PRICE_TIERS, NsdError::TooManyPriceTiers);
+        if let Some(first) = self.price_tiers.first() {
+            require!(first.start_supply == 0, NsdError::InvalidPriceTiers);
+        }
+        for pair in self.price_tiers.windows(2) {
+            require!(pair[0].start_supply < pair[1].start_supply, NsdError::InvalidPriceTiers);
+        }
+        for tier in &self.price_tiers {
+            self.require_price_in_band(tier.price)?;
+        }
+        
+        // Only one of the tiers, the bonding curve and the Dutch auction may be set
+        let pricing_modes = [!self.price_tiers.is_empty(), self.bonding_curve.is_some(), self.dutch_auction.is_some()];
+        require!(pricing_modes.iter().filter(|set| **set).count() <= 1, NsdError::PricingModeConflict);
+        
+        // The bonding curve stays within the band up to the supply cap
+        if let Some(curve) = self.bonding_curve {
+            self.require_price_in_band(curve.base_price)?;
+            self.require_price_in_band(curve.price_at(self.max_supply.saturating_sub(1))?)?;
+        }
+        
+        // Payment mints are unique and never mixed with the bonding curve, whose refunds are in lamports
+        require!(self.payment_mints.len() <= MAX_PAYMENT_MINTS, NsdError::TooManyPaymentMints);
+        for (index, payment) in self.payment_mints.iter().enumerate() {
+            require!(
+                self.payment_mints[..index].iter().all(|other| other.mint != payment.mint),
+                NsdError::DuplicatePaymentMint
+            );
+        }
+        require!(
+            self.payment_mints.is_empty() || self.bonding_curve.is_none(),
+            NsdError::CurveRequiresLamports
+        );
+        
+        // The Dutch auction decays from its start price down to a floor within the band
+        if let Some(auction) = self.dutch_auction {
+            require!(auction.floor_price <= auction.start_price, NsdError::InvalidDutchAuction);
+            self.require_price_in_band(auction.start_price)?;
+            self.require_price_in_band(auction.floor_price)?;
+        }
+        Ok(())
+    }
+    
+    /// Check that a price lies within the configured price band
+    /// 
+    /// Applies to the mint price, every price tier, the bonding curve, the Dutch auction and the price of every sale phase.
+    /// 
+    /// # Parameters
+    /// - `price` - Price per token in lamports
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn require_price_in_band(&self, price: u64) -> Result<()> {
+        require!(self.min_price.is_none_or(|min| price >= min), NsdError::PriceOutOfBand);
+        require!(self.max_price.is_none_or(|max| price <= max), NsdError::PriceOutOfBand);
+        Ok(())
+    }
+    
+    /// Snapshot of the admin-settable sale parameters
+    /// 
+    /// # Returns
+    /// - `ConfigSettings` - The current settings
+    pub fn settings(&self) -> ConfigSettings {
+        ConfigSettings {
+            max_supply: self.max_supply,
+            mint_price: self.mint_price,
+            is_active: self.is_active,
+            max_per_wallet: self.max_per_wallet,
+            max_per_transaction: self.max_per_transaction,
+            cooldown_seconds: self.cooldown_seconds,
+            allowlist_root: self.allowlist_root,
+        }
+    }
+    
+    /// Record a completed mint in the configuration
+    /// 
+    /// # Parameters
+    /// - `mint_amount` - Number of tokens minted
+    /// - `total_cost` - Lamports collected into the treasury, zero for token payments
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn record_mint(&mut self, mint_amount: u64, total_cost: u64) -> Result<()> {
+        // Increment the total minted count
+        self.total_minted = self.total_minted
+            .checked_add(mint_amount)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        // Record the lamports collected for this mint
+        self.total_revenue = self.total_revenue
+            .checked_add(total_cost)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        self.validate()
+    }
+    
+    /// Record tokens sold back along the bonding curve
+    /// 
+    /// # Parameters
+    /// - `sell_amount` - Number of tokens burned
+    /// - `refund` - Lamports paid out of the treasury
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn record_sell(&mut self, sell_amount: u64, refund: u64) -> Result<()> {
+        // Return the tokens to the unsold supply
+        self.total_minted = self.total_minted
+            .checked_sub(sell_amount)
+            .ok_or(NsdError::ExceedsCurveSupply)?;
+        // Record the lamports refunded for this sell
+        self.total_refunded = self.total_refunded
+            .checked_add(refund)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
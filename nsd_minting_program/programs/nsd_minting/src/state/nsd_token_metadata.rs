@@ -8,7 +8,10 @@
 //! account management, error handling, and security.
 
 // Import required modules and macros
+use crate::constants::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
+use crate::error::NsdError;
 use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
 
 /// Token metadata structure for NSD tokens
 /// 
@@ -30,4 +33,63 @@ pub struct NsdTokenMetadata {
     
     /// Bump seed for the metadata account
     pub bump: u8,
+    
+    /// Whether the metadata has been permanently locked
+    pub is_locked: bool,
+}
+
+/// Implementation of helper methods for NsdTokenMetadata
+impl NsdTokenMetadata {
+    /// Account size for the given string values
+    /// 
+    /// The account is sized to its contents and reallocated whenever they change.
+    /// 
+    /// # Parameters
+    /// - `name` - Name of the token
+    /// - `symbol` - Symbol of the token
+    /// - `uri` - URI for token metadata
+    /// 
+    /// # Returns
+    /// - `usize` - Account size in bytes, including the discriminator
+    pub fn space(name: &str, symbol: &str, uri: &str) -> usize {
+        8 + 32 + (4 + name.len()) + (4 + symbol.len()) + (4 + uri.len()) + 1 + 1
+    }
+    
+    /// Metaplex metadata data mirroring this account
+    /// 
+    /// This account is the canonical on-chain copy; the Metaplex metadata account
+    /// is kept in sync with it so wallets and explorers display the token.
+    /// 
+    /// # Returns
+    /// - `DataV2` - The Metaplex metadata values
+    pub fn metaplex_data(&self) -> DataV2 {
+        DataV2 {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        }
+    }
+    
+    /// Check the string values against their maximum lengths
+    /// 
+    /// # Parameters
+    /// - `name` - Name of the token
+    /// - `symbol` - Symbol of the token
+    /// - `uri` - URI for token metadata
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn validate(name: &str, symbol: &str, uri: &str) -> Result<()> {
+        // Check the name length
+        require!(name.len() <= MAX_NAME_LENGTH, NsdError::NameTooLong);
+        // Check the symbol length
+        require!(symbol.len() <= MAX_SYMBOL_LENGTH, NsdError::SymbolTooLong);
+        // Check the URI length
+        require!(uri.len() <= MAX_URI_LENGTH, NsdError::UriTooLong);
+        Ok(())
+    }
 }
\ No newline at end of file
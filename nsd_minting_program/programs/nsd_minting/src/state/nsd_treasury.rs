@@ -0,0 +1,24 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use anchor_lang::prelude::*;
+
+/// Treasury structure for NSD minting proceeds
+/// 
+/// This program-owned account receives the lamports paid for every mint.
+/// Funds can only leave it through the admin-only withdraw_treasury instruction.
+#[account]
+pub struct NsdTreasury {
+    /// The configuration account this treasury belongs to
+    pub config: Pubkey,
+    
+    /// Bump seed for the treasury account
+    pub bump: u8,
+}
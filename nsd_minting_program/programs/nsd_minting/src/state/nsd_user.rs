@@ -8,6 +8,7 @@
 //! account management, error handling, and security.
 
 // Import required modules and macros
+use crate::error::NsdError;
 use anchor_lang::prelude::*;
 
 /// User minting history structure
@@ -27,4 +28,31 @@ pub struct NsdUser {
     
     /// Bump seed for the user account
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+/// Implementation of helper methods for NsdUser
+impl NsdUser {
+    /// Record a completed mint in the user's history
+    /// 
+    /// # Parameters
+    /// - `user` - The user's public key
+    /// - `mint_amount` - Number of tokens minted
+    /// - `timestamp` - Unix timestamp of the mint
+    /// - `bump` - Bump seed for the user account
+    /// 
+    /// # Returns
+    /// - `Result<()>` - Success or error
+    pub fn record_mint(&mut self, user: Pubkey, mint_amount: u64, timestamp: i64, bump: u8) -> Result<()> {
+        // Set the user's public key
+        self.user = user;
+        // Increment the user's minted token count
+        self.tokens_minted = self.tokens_minted
+            .checked_add(mint_amount)
+            .ok_or(NsdError::ArithmeticOverflow)?;
+        // Set the timestamp of the last mint
+        self.last_mint_timestamp = timestamp;
+        // Store the bump seed for the user account
+        self.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+
+// Import required modules and macros
+use anchor_lang::prelude::*;
+
+/// Payment mint structure
+/// 
+/// One SPL token accepted as payment by `mint_tokens_with_token`, such as a stablecoin,
+/// with the flat price charged per NSD token in that token.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PaymentMint {
+    /// Mint address of the accepted payment token
+    pub mint: Pubkey,
+    
+    /// Price per NSD token in base units of the payment token
+    pub price: u64,
+}
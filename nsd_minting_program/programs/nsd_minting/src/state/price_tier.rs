@@ -0,0 +1,25 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+
+// Import required modules and macros
+use anchor_lang::prelude::*;
+
+/// Price tier structure
+/// 
+/// One entry of a sale's price-tier table. The tier applies from `start_supply` tokens sold
+/// up to the `start_supply` of the next tier; the last tier has no upper bound.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceTier {
+    /// Cumulative number of tokens sold at which this tier starts (inclusive)
+    pub start_supply: u64,
+    
+    /// Price per NSD token in lamports within this tier
+    pub price: u64,
+}
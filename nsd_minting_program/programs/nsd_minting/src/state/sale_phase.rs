@@ -0,0 +1,56 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use anchor_lang::prelude::*;
+
+/// Time-boxed sale phase structure
+/// 
+/// This struct stores one window of the sale with its own price and limits.
+/// While any phase exists, mints are only accepted inside an active phase window.
+#[account]
+pub struct SalePhase {
+    /// The configuration account this phase belongs to
+    pub config: Pubkey,
+    
+    /// Identifier of the phase within its configuration
+    pub phase_id: u8,
+    
+    /// Unix timestamp at which the phase opens (inclusive)
+    pub start_timestamp: i64,
+    
+    /// Unix timestamp at which the phase closes (exclusive)
+    pub end_timestamp: i64,
+    
+    /// Price per NSD token in lamports during this phase
+    pub price: u64,
+    
+    /// Maximum number of tokens a single wallet may hold from minting, if capped
+    pub max_per_wallet: Option<u64>,
+    
+    /// Merkle root of the phase allowlist, only allowlisted wallets may mint while set
+    pub allowlist_root: Option<[u8; 32]>,
+    
+    /// Bump seed for the sale phase account
+    pub bump: u8,
+}
+
+/// Implementation of helper methods for SalePhase
+impl SalePhase {
+    /// Whether the phase window contains the given timestamp
+    /// 
+    /// # Parameters
+    /// - `now` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// - `bool` - Whether the phase is active
+    pub fn is_active_at(&self, now: i64) -> bool {
+        self.start_timestamp <= now && now < self.end_timestamp
+    }
+}
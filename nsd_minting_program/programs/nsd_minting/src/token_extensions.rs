@@ -0,0 +1,140 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+
+// Import required modules and macros
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint,
+};
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+
+/// Check whether a mint is owned by the Token-2022 program
+/// 
+/// # Parameters
+/// - `mint` - The token mint account
+/// 
+/// # Returns
+/// - `bool` - True for Token-2022 mints, false for SPL Token mints
+pub fn is_token_2022(mint: &AccountInfo) -> bool {
+    *mint.owner == spl_token_2022::ID
+}
+
+/// Size of a Token-2022 mint carrying the metadata-pointer extension
+/// 
+/// The token-metadata extension itself is appended later by the Token-2022 program,
+/// which reallocates the mint to fit the metadata values.
+/// 
+/// # Returns
+/// - `Result<usize>` - Mint account size in bytes
+pub fn mint_space_with_metadata_pointer() -> Result<usize> {
+    Ok(ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])?)
+}
+
+/// Token-metadata extension values for a mint
+/// 
+/// # Parameters
+/// - `mint` - The token mint address
+/// - `update_authority` - The authority allowed to update the metadata
+/// - `name` - Name of the token
+/// - `symbol` - Symbol of the token
+/// - `uri` - URI for token metadata
+/// 
+/// # Returns
+/// - `Result<TokenMetadata>` - The extension values
+pub fn token_metadata(
+    mint: Pubkey,
+    update_authority: Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Result<TokenMetadata> {
+    Ok(TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(update_authority))?,
+        mint,
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        uri: uri.to_string(),
+        additional_metadata: vec![],
+    })
+}
+
+/// Token-metadata extension values currently stored in a Token-2022 mint
+/// 
+/// # Parameters
+/// - `mint` - The Token-2022 mint account
+/// 
+/// # Returns
+/// - `Result<TokenMetadata>` - The stored values, or an error if the mint has no metadata
+pub fn read_token_metadata(mint: &AccountInfo) -> Result<TokenMetadata> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    Ok(state.get_variable_len_extension::<TokenMetadata>()?)
+}
+
+/// Lamports a Token-2022 mint is missing to stay rent-exempt once its
+/// token-metadata extension holds the given values
+/// 
+/// The Token-2022 program reallocates the mint but does not fund it, so the
+/// shortfall must be transferred to the mint before the metadata CPI.
+/// 
+/// # Parameters
+/// - `mint` - The Token-2022 mint account
+/// - `metadata` - The metadata values about to be written
+/// 
+/// # Returns
+/// - `Result<u64>` - Lamports to add to the mint
+pub fn metadata_rent_shortfall(mint: &AccountInfo, metadata: &TokenMetadata) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    // Replace the size of any metadata already stored in the mint
+    let current_size = match state.get_variable_len_extension::<TokenMetadata>() {
+        Ok(current) => current.tlv_size_of()?,
+        Err(_) => 0,
+    };
+    let new_len = data
+        .len()
+        .saturating_sub(current_size)
+        .saturating_add(metadata.tlv_size_of()?);
+    let required = Rent::get()?.minimum_balance(new_len);
+    Ok(required.saturating_sub(mint.lamports()))
+}
+
+/// Fund a Token-2022 mint for the metadata about to be written into it
+/// 
+/// # Parameters
+/// - `payer` - The account paying the additional rent
+/// - `mint` - The Token-2022 mint account
+/// - `system_program` - The System program
+/// - `metadata` - The metadata values about to be written
+/// 
+/// # Returns
+/// - `Result<()>` - Success or error
+pub fn fund_metadata_rent<'info>(
+    payer: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    metadata: &TokenMetadata,
+) -> Result<()> {
+    let shortfall = metadata_rent_shortfall(mint, metadata)?;
+    if shortfall == 0 {
+        return Ok(());
+    }
+    anchor_lang::system_program::transfer(
+        CpiContext::new(system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: mint.clone(),
+            }
+        ),
+        shortfall,
+    )
+}
@@ -0,0 +1,174 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Admin tests: `initialize_config`, `bind_mint_authority`, `update_config`, `withdraw_treasury`
+//! and the admin handover. Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use anchor_spl::token_interface::TokenAccount;
+use common::*;
+use nsd_minting::{NsdConfig, NsdError, UpdateConfigArgs};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PRICE: u64 = 1_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn external_mint_must_be_bound_before_minting() {
+    let mut context = start().await;
+    let payer = context.payer.pubkey();
+    let token_mint = create_external_mint(&mut context, &payer, 6).await;
+    let sale = Sale::new(token_mint, anchor_spl::token::ID);
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let instruction = initialize_config(&context, &sale, 100, PRICE);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.token_mint, sale.mint());
+    assert!(!config.mint_authority_bound);
+
+    let instruction = mint_tokens(&sale, &buyer.pubkey(), None, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::MintAuthorityNotBound);
+
+    // The mint authority still belongs to the payer
+    let instruction = bind_mint_authority(&context, &sale, &payer);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidMintAuthority);
+
+    let instruction = set_mint_authority(&context, &sale.mint(), &sale.mint_authority);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = bind_mint_authority(&context, &sale, &payer);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert!(config.mint_authority_bound);
+    assert_eq!(config.decimals, 6);
+
+    let instruction = mint_tokens(&sale, &buyer.pubkey(), None, 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let token_account: TokenAccount = fetch(&mut context, sale.user_token_account(&buyer.pubkey())).await;
+    assert_eq!(token_account.amount, 2);
+}
+
+#[tokio::test]
+async fn update_config_changes_only_provided_fields() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+
+    let args = UpdateConfigArgs { max_supply: Some(20), mint_price: Some(2 * PRICE), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.max_supply, 20);
+    assert_eq!(config.mint_price, 2 * PRICE);
+    assert!(config.is_active);
+    assert_eq!(config.max_per_wallet, None);
+}
+
+#[tokio::test]
+async fn update_config_rejects_non_admin() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let intruder = Keypair::new();
+
+    let args = UpdateConfigArgs { mint_price: Some(0), ..Default::default() };
+    let instruction = update_config(&context, &sale, &intruder.pubkey(), args);
+    assert_nsd_error(send(&mut context, instruction, &[&intruder]).await, NsdError::Unauthorized);
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.mint_price, PRICE);
+}
+
+#[tokio::test]
+async fn withdraw_treasury_keeps_it_rent_exempt() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let admin = context.payer.pubkey();
+    let destination = Keypair::new().pubkey();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 5);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let instruction = withdraw_treasury(&context, &sale, &admin, &destination, 5 * PRICE + 1);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InsufficientTreasuryBalance);
+
+    let intruder = Keypair::new();
+    let instruction = withdraw_treasury(&context, &sale, &intruder.pubkey(), &intruder.pubkey(), PRICE);
+    assert_nsd_error(send(&mut context, instruction, &[&intruder]).await, NsdError::Unauthorized);
+
+    // The destination must end up rent-exempt as a plain system account
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instruction = solana_system_interface::instruction::transfer(&admin, &destination, rent.minimum_balance(0));
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = withdraw_treasury(&context, &sale, &admin, &destination, 5 * PRICE);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    assert_eq!(balance(&mut context, destination).await, rent.minimum_balance(0) + 5 * PRICE);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_withdrawn, 5 * PRICE);
+}
+
+#[tokio::test]
+async fn admin_handover_requires_the_proposed_key() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let admin = context.payer.pubkey();
+    let new_admin = Keypair::new();
+    let intruder = Keypair::new();
+
+    let instruction = accept_admin(&context, &sale, &new_admin.pubkey());
+    assert_nsd_error(send(&mut context, instruction, &[&new_admin]).await, NsdError::NoPendingAdmin);
+
+    let instruction = propose_admin(&context, &sale, &intruder.pubkey(), intruder.pubkey());
+    assert_nsd_error(send(&mut context, instruction, &[&intruder]).await, NsdError::Unauthorized);
+
+    let instruction = propose_admin(&context, &sale, &admin, new_admin.pubkey());
+    send(&mut context, instruction, &[]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.pending_admin, Some(new_admin.pubkey()));
+
+    let instruction = accept_admin(&context, &sale, &intruder.pubkey());
+    assert_nsd_error(send(&mut context, instruction, &[&intruder]).await, NsdError::InvalidPendingAdmin);
+
+    let instruction = accept_admin(&context, &sale, &new_admin.pubkey());
+    send(&mut context, instruction, &[&new_admin]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, None);
+
+    // The old admin lost its rights
+    let args = UpdateConfigArgs { mint_price: Some(0), ..Default::default() };
+    let instruction = update_config(&context, &sale, &admin, args);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::Unauthorized);
+}
+
+#[tokio::test]
+async fn cancel_admin_transfer_clears_the_proposal() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let admin = context.payer.pubkey();
+    let new_admin = Keypair::new();
+
+    let instruction = cancel_admin_transfer(&context, &sale, &admin);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::NoPendingAdmin);
+
+    let instruction = propose_admin(&context, &sale, &admin, new_admin.pubkey());
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = cancel_admin_transfer(&context, &sale, &admin);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = accept_admin(&context, &sale, &new_admin.pubkey());
+    assert_nsd_error(send(&mut context, instruction, &[&new_admin]).await, NsdError::NoPendingAdmin);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.admin, admin);
+}
@@ -0,0 +1,187 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//!
+//! Bonding curve tests: `set_bonding_curve`, curve-priced mints, `sell_tokens` and the treasury reserve.
+//! Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use common::*;
+use nsd_minting::{BondingCurve, NsdConfig, NsdError, PriceTier, UpdateConfigArgs};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PRICE: u64 = 1_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+const CURVE: BondingCurve = BondingCurve { base_price: 1_000, slope: 100 };
+
+#[tokio::test]
+async fn mints_and_sells_walk_the_same_curve() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(CURVE));
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    // Tokens 0..10 cost 1000, 1100, ..., 1900
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let treasury_before = balance(&mut context, sale.treasury).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 10);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, sale.treasury).await - treasury_before, 14_500);
+
+    // Selling the top 4 tokens refunds 1600 + 1700 + 1800 + 1900
+    let seller_before = balance(&mut context, buyer.pubkey()).await;
+    let instruction = sell_tokens(&sale, &buyer.pubkey(), 4, 7_000);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, buyer.pubkey()).await - seller_before, 7_000);
+    assert_eq!(balance(&mut context, sale.treasury).await - treasury_before, 7_500);
+
+    let token_account: TokenAccount = fetch(&mut context, sale.user_token_account(&buyer.pubkey())).await;
+    assert_eq!(token_account.amount, 6);
+    let mint: Mint = fetch(&mut context, sale.mint()).await;
+    assert_eq!(mint.supply, 6);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_minted, 6);
+    assert_eq!(config.total_refunded, 7_000);
+
+    // Buying back the same range costs exactly the refund, and the buyer's max-in caps the buy
+    assert_eq!(config.quote(None, 4, 0).unwrap(), 7_000);
+    let instruction = mint_tokens_v2_capped(&sale, &buyer.pubkey(), None, 4, 6_999);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::PriceExceedsLimit);
+    let instruction = mint_tokens_v2_capped(&sale, &buyer.pubkey(), None, 4, 7_000);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+}
+
+#[tokio::test]
+async fn sells_are_guarded_by_min_out() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 100, PRICE).await;
+
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(CURVE));
+    send(&mut context, instruction, &[]).await.unwrap();
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 5);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // The top token of a supply of 5 refunds 1400
+    let instruction = sell_tokens(&sale, &buyer.pubkey(), 1, 1_401);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::SlippageExceeded);
+    let instruction = sell_tokens(&sale, &buyer.pubkey(), 6, 0);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ExceedsCurveSupply);
+    let instruction = sell_tokens(&sale, &buyer.pubkey(), 1, 1_400);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // Pausing the sale pauses selling
+    let args = UpdateConfigArgs { is_active: Some(false), ..Default::default() };
+    let instruction = update_config(&context, &sale, &admin, args);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = sell_tokens(&sale, &buyer.pubkey(), 1, 0);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::MintingNotActive);
+}
+
+#[tokio::test]
+async fn withdrawals_keep_the_curve_reserve() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(CURVE));
+    send(&mut context, instruction, &[]).await.unwrap();
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 5);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // Every lamport paid in is needed to buy the supply back
+    let destination = Keypair::new().pubkey();
+    let instruction = withdraw_treasury(&context, &sale, &admin, &destination, 1);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InsufficientTreasuryBalance);
+
+    // Clearing or changing the curve would release the reserve
+    let instruction = set_bonding_curve(&context, &sale, &admin, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::BondingCurveLocked);
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(BondingCurve { base_price: 1_000, slope: 0 }));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::BondingCurveLocked);
+}
+
+#[tokio::test]
+async fn curve_is_fixed_once_tokens_are_minted() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+
+    // No curve, nothing to sell against
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let instruction = sell_tokens(&sale, &buyer.pubkey(), 1, 0);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::BondingCurveNotSet);
+
+    // Tokens bought at the flat price have no reserve behind them
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(CURVE));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::BondingCurveLocked);
+}
+
+#[tokio::test]
+async fn curve_is_exclusive_with_tiers_and_stays_in_the_band() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale_with_band(&mut context, anchor_spl::token::ID, 100, PRICE, Some(500), Some(10_000))
+        .await
+        .unwrap();
+
+    // Token 99 would cost 1000 + 99 * 100 = 10900
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(CURVE));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PriceOutOfBand);
+    let curve = BondingCurve { base_price: 1_000, slope: 90 };
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(curve));
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = set_price_tiers(&context, &sale, &admin, vec![PriceTier { start_supply: 0, price: PRICE }]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PricingModeConflict);
+
+    let outsider = Keypair::new();
+    let instruction = set_bonding_curve(&context, &sale, &outsider.pubkey(), None);
+    assert_nsd_error(send(&mut context, instruction, &[&outsider]).await, NsdError::Unauthorized);
+}
+
+#[tokio::test]
+async fn curve_needs_the_whole_supply_minted_by_the_sale() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+
+    // Tokens minted before the program took over the mint authority were never paid in
+    let sale = Sale::new(create_external_mint(&mut context, &admin, 0).await, anchor_spl::token::ID);
+    let holder = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    fund_token_account(&mut context, &sale.mint(), &holder.pubkey(), 10).await;
+    let instruction = initialize_config(&context, &sale, 100, PRICE);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(CURVE));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::UnbackedMintSupply);
+
+    // A curve set before the outside mint cannot be sold against once it lands
+    let sale = Sale::new(create_external_mint(&mut context, &admin, 0).await, anchor_spl::token::ID);
+    let instruction = initialize_config(&context, &sale, 100, PRICE);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(CURVE));
+    send(&mut context, instruction, &[]).await.unwrap();
+    fund_token_account(&mut context, &sale.mint(), &holder.pubkey(), 10).await;
+    let instruction = set_mint_authority(&context, &sale.mint(), &sale.mint_authority);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = bind_mint_authority(&context, &sale, &admin);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &holder.pubkey(), None, 5);
+    send(&mut context, instruction, &[&holder]).await.unwrap();
+    let instruction = sell_tokens(&sale, &holder.pubkey(), 5, 0);
+    assert_nsd_error(send(&mut context, instruction, &[&holder]).await, NsdError::UnbackedMintSupply);
+}
@@ -0,0 +1,834 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Shared `solana-program-test` harness: test validator setup, transaction helpers and one
+//! instruction builder per program instruction. Each test binary uses a different subset.
+#![allow(dead_code)]
+
+// Import required modules and macros
+use anchor_lang::solana_program::{hash::hashv, program_pack::Pack};
+use anchor_lang::{system_program, AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::metadata::mpl_token_metadata;
+use anchor_spl::token::spl_token;
+use nsd_minting::{BondingCurve, DutchAuction, NsdError, PaymentMint, PriceTier, UpdateConfigArgs};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use solana_system_interface::instruction as system_instruction;
+
+/// Location of the Metaplex Token Metadata program dump, see the README
+pub const METAPLEX_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mpl_token_metadata.so");
+
+/// Start a test validator with the NSD minting program
+///
+/// SPL Token, Token-2022 and the Associated Token program are built into the test validator.
+pub async fn start() -> ProgramTestContext {
+    ProgramTest::new("nsd_minting", nsd_minting::ID, None).start_with_context().await
+}
+
+/// Start a test validator with the NSD minting and Metaplex Token Metadata programs
+///
+/// Panics when the Metaplex dump has not been fetched with `scripts/fetch-fixtures.sh`.
+pub async fn start_with_metaplex() -> ProgramTestContext {
+    assert!(
+        std::path::Path::new(METAPLEX_FIXTURE).exists(),
+        "{METAPLEX_FIXTURE} not found, fetch it with scripts/fetch-fixtures.sh"
+    );
+    let mut program_test = ProgramTest::new("nsd_minting", nsd_minting::ID, None);
+    program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, None);
+    program_test.start_with_context().await
+}
+
+/// Sign and send a transaction paid for by the context payer
+pub async fn send(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    // A fresh blockhash keeps repeated identical instructions from being deduplicated
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Simulate a view instruction and decode its `u64` return data
+pub async fn simulate_u64(context: &mut ProgramTestContext, instruction: Instruction) -> Result<u64, BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(transaction).await?;
+    if let Some(Err(err)) = simulation.result {
+        return Err(BanksClientError::TransactionError(err));
+    }
+    let return_data = simulation.simulation_details.and_then(|details| details.return_data).expect("no return data");
+    Ok(u64::from_le_bytes(return_data.data.try_into().unwrap()))
+}
+
+/// Assert that a transaction failed with the given program error
+pub fn assert_nsd_error(result: Result<(), BanksClientError>, error: NsdError) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(error.into())),
+    );
+}
+
+/// Fetch and deserialize an Anchor or token account
+pub async fn fetch<T: AccountDeserialize>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// Lamport balance of an account
+pub async fn balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    context.banks_client.get_balance(address).await.unwrap()
+}
+
+/// Create a keypair funded by the context payer
+pub async fn funded_keypair(context: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let keypair = Keypair::new();
+    let instruction = system_instruction::transfer(&context.payer.pubkey(), &keypair.pubkey(), lamports);
+    send(context, instruction, &[]).await.unwrap();
+    keypair
+}
+
+/// Current cluster unix timestamp
+pub async fn now(context: &mut ProgramTestContext) -> i64 {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+}
+
+/// Move the cluster clock to the given unix timestamp
+pub async fn set_time(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+/// Merkle parent of two allowlist nodes, hashed in sorted order like the program
+pub fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[&a, &b]).to_bytes()
+    } else {
+        hashv(&[&b, &a]).to_bytes()
+    }
+}
+
+/// Create an SPL Token mint outside the program, controlled by `authority`
+pub async fn create_external_mint(context: &mut ProgramTestContext, authority: &Pubkey, decimals: u8) -> Keypair {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::ID,
+    );
+    send(context, create, &[&mint]).await.unwrap();
+    let initialize = spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), authority, None, decimals).unwrap();
+    send(context, initialize, &[]).await.unwrap();
+    mint
+}
+
+/// Hand an external mint's authority to a new key, signed by the context payer
+pub fn set_mint_authority(context: &ProgramTestContext, mint: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    spl_token::instruction::set_authority(
+        &spl_token::ID,
+        mint,
+        Some(new_authority),
+        spl_token::instruction::AuthorityType::MintTokens,
+        &context.payer.pubkey(),
+        &[],
+    )
+    .unwrap()
+}
+
+/// Create `owner`'s associated token account for an external mint and mint `amount` into it
+///
+/// The context payer must be the mint authority, as with `create_external_mint`.
+pub async fn fund_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+    let create = anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account(
+        &context.payer.pubkey(),
+        owner,
+        mint,
+        &spl_token::ID,
+    );
+    send(context, create, &[]).await.unwrap();
+    let token_account = get_associated_token_address_with_program_id(owner, mint, &spl_token::ID);
+    let mint_to = spl_token::instruction::mint_to(&spl_token::ID, mint, &token_account, &context.payer.pubkey(), &[], amount).unwrap();
+    send(context, mint_to, &[]).await.unwrap();
+    token_account
+}
+
+/// Addresses of a sale and its PDAs
+pub struct Sale {
+    pub token_mint: Keypair,
+    pub token_program: Pubkey,
+    pub config: Pubkey,
+    pub treasury: Pubkey,
+    pub mint_authority: Pubkey,
+    pub freeze_authority: Pubkey,
+    pub metadata: Pubkey,
+    pub metaplex_metadata: Pubkey,
+}
+
+impl Sale {
+    /// Derive the addresses of a sale for `token_mint` under `token_program`
+    pub fn new(token_mint: Keypair, token_program: Pubkey) -> Self {
+        let (config, _) = Pubkey::find_program_address(
+            &[b"nsd_config", token_mint.pubkey().as_ref()],
+            &nsd_minting::ID,
+        );
+        let (treasury, _) = Pubkey::find_program_address(&[b"treasury", config.as_ref()], &nsd_minting::ID);
+        let (mint_authority, _) = Pubkey::find_program_address(&[b"mint_authority", config.as_ref()], &nsd_minting::ID);
+        let (freeze_authority, _) = Pubkey::find_program_address(&[b"freeze_authority", config.as_ref()], &nsd_minting::ID);
+        let (metadata, _) = Pubkey::find_program_address(
+            &[b"metadata", token_mint.pubkey().as_ref()],
+            &nsd_minting::ID,
+        );
+        let (metaplex_metadata, _) = mpl_token_metadata::accounts::Metadata::find_pda(&token_mint.pubkey());
+        Self { token_mint, token_program, config, treasury, mint_authority, freeze_authority, metadata, metaplex_metadata }
+    }
+
+    /// The token mint address
+    pub fn mint(&self) -> Pubkey {
+        self.token_mint.pubkey()
+    }
+
+    /// The minting history account of `user`
+    pub fn user_account(&self, user: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"nsd_user", self.config.as_ref(), user.as_ref()], &nsd_minting::ID).0
+    }
+
+    /// The associated token account of `user` for this sale's mint
+    pub fn user_token_account(&self, user: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(user, &self.mint(), &self.token_program)
+    }
+
+    /// The treasury's associated token account for an SPL Token payment mint
+    pub fn treasury_payment_account(&self, payment_mint: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(&self.treasury, payment_mint, &spl_token::ID)
+    }
+
+    /// The sale phase account with the given id
+    pub fn sale_phase(&self, phase_id: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"sale_phase", self.config.as_ref(), phase_id.to_le_bytes().as_ref()],
+            &nsd_minting::ID,
+        )
+        .0
+    }
+}
+
+/// Create a sale and its mint through `initialize_sale`, administered by the context payer
+pub async fn initialize_sale(context: &mut ProgramTestContext, token_program: Pubkey, max_supply: u64, mint_price: u64) -> Sale {
+    initialize_sale_with_band(context, token_program, max_supply, mint_price, None, None).await.unwrap()
+}
+
+/// `initialize_sale` with a price band, returning the transaction result
+pub async fn initialize_sale_with_band(
+    context: &mut ProgramTestContext,
+    token_program: Pubkey,
+    max_supply: u64,
+    mint_price: u64,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+) -> Result<Sale, BanksClientError> {
+    let sale = Sale::new(Keypair::new(), token_program);
+    let instruction = Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::InitializeSale {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            treasury: sale.treasury,
+            admin: context.payer.pubkey(),
+            token_mint: sale.mint(),
+            mint_authority: sale.mint_authority,
+            freeze_authority: sale.freeze_authority,
+            token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::InitializeSale {
+            decimals: 0,
+            max_supply,
+            mint_price,
+            min_price,
+            max_price,
+        }
+        .data(),
+    };
+    send(context, instruction, &[&sale.token_mint]).await?;
+    Ok(sale)
+}
+
+pub fn initialize_config(context: &ProgramTestContext, sale: &Sale, max_supply: u64, mint_price: u64) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::InitializeConfig {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            treasury: sale.treasury,
+            admin: context.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::InitializeConfig {
+            token_mint: sale.mint(),
+            max_supply,
+            mint_price,
+            min_price: None,
+            max_price: None,
+        }
+        .data(),
+    }
+}
+
+pub fn bind_mint_authority(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::BindMintAuthority {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+            token_mint: sale.mint(),
+            mint_authority: sale.mint_authority,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::BindMintAuthority {}.data(),
+    }
+}
+
+pub fn update_config(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, args: UpdateConfigArgs) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::UpdateConfig {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::UpdateConfig { args }.data(),
+    }
+}
+
+pub fn freeze_max_supply(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::FreezeMaxSupply {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::FreezeMaxSupply {}.data(),
+    }
+}
+
+pub fn set_price_tiers(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, tiers: Vec<PriceTier>) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::SetPriceTiers {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::SetPriceTiers { tiers }.data(),
+    }
+}
+
+pub fn set_bonding_curve(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, curve: Option<BondingCurve>) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::SetBondingCurve {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            token_mint: sale.mint(),
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::SetBondingCurve { curve }.data(),
+    }
+}
+
+pub fn sell_tokens(sale: &Sale, user: &Pubkey, sell_amount: u64, min_lamports_out: u64) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::SellTokens {
+            user: *user,
+            config: sale.config,
+            treasury: sale.treasury,
+            token_mint: sale.mint(),
+            user_token_account: sale.user_token_account(user),
+            token_program: sale.token_program,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::SellTokens { sell_amount, min_lamports_out }.data(),
+    }
+}
+
+pub fn set_dutch_auction(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, auction: Option<DutchAuction>) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::SetDutchAuction {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::SetDutchAuction { auction }.data(),
+    }
+}
+
+pub fn current_price(sale: &Sale, sale_phase: Option<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::CurrentPrice { config: sale.config, sale_phase }.to_account_metas(None),
+        data: nsd_minting::instruction::CurrentPrice {}.data(),
+    }
+}
+
+pub fn quote_mint(sale: &Sale, sale_phase: Option<Pubkey>, mint_amount: u64) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::CurrentPrice { config: sale.config, sale_phase }.to_account_metas(None),
+        data: nsd_minting::instruction::QuoteMint { mint_amount }.data(),
+    }
+}
+
+pub fn finalize_sale(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, destination: Option<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::FinalizeSale {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            treasury: sale.treasury,
+            admin: *admin,
+            token_mint: sale.mint(),
+            mint_authority: sale.mint_authority,
+            destination,
+            token_program: sale.token_program,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::FinalizeSale {}.data(),
+    }
+}
+
+pub fn withdraw_treasury(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::WithdrawTreasury {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            treasury: sale.treasury,
+            admin: *admin,
+            destination: *destination,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::WithdrawTreasury { amount }.data(),
+    }
+}
+
+pub fn set_payment_mints(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, payment_mints: Vec<PaymentMint>) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::SetPaymentMints {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::SetPaymentMints { payment_mints }.data(),
+    }
+}
+
+/// `mint_tokens_with_token` for `user`, paying from their associated token account for the SPL Token `payment_mint`
+pub fn mint_tokens_with_token(
+    sale: &Sale,
+    user: &Pubkey,
+    payment_mint: &Pubkey,
+    sale_phase: Option<Pubkey>,
+    mint_amount: u64,
+    max_total_cost: u64,
+) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::MintTokensWithToken {
+            fee_payer: *user,
+            config: sale.config,
+            treasury: sale.treasury,
+            token_mint: sale.mint(),
+            user: *user,
+            user_account: sale.user_account(user),
+            user_token_account: sale.user_token_account(user),
+            mint_authority: sale.mint_authority,
+            token_program: sale.token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            payment_mint: *payment_mint,
+            user_payment_account: get_associated_token_address_with_program_id(user, payment_mint, &spl_token::ID),
+            treasury_payment_account: sale.treasury_payment_account(payment_mint),
+            payment_token_program: spl_token::ID,
+            sale_phase,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::MintTokensWithToken { mint_amount, max_total_cost }.data(),
+    }
+}
+
+pub fn withdraw_payment_tokens(
+    context: &ProgramTestContext,
+    sale: &Sale,
+    admin: &Pubkey,
+    payment_mint: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::WithdrawPaymentTokens {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            treasury: sale.treasury,
+            admin: *admin,
+            payment_mint: *payment_mint,
+            treasury_payment_account: sale.treasury_payment_account(payment_mint),
+            destination: *destination,
+            payment_token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::WithdrawPaymentTokens { amount }.data(),
+    }
+}
+
+/// `mint_tokens` accounts for `user`, who also pays for the mint
+pub fn mint_tokens_accounts(sale: &Sale, user: &Pubkey, sale_phase: Option<Pubkey>) -> nsd_minting::accounts::MintTokens {
+    let user_token_account = sale.user_token_account(user);
+    nsd_minting::accounts::MintTokens {
+        fee_payer: *user,
+        config: sale.config,
+        treasury: sale.treasury,
+        user_token_account,
+        user: *user,
+        user_account: sale.user_account(user),
+        token_mint: sale.mint(),
+        system_program: system_program::ID,
+        mint: sale.mint(),
+        assoc_token_account: user_token_account,
+        mint_authority: sale.mint_authority,
+        wallet: *user,
+        token_program: sale.token_program,
+        associated_token_program: anchor_spl::associated_token::ID,
+        sale_phase,
+    }
+}
+
+/// `mint_tokens` without a cost limit
+pub fn mint_tokens(sale: &Sale, user: &Pubkey, sale_phase: Option<Pubkey>, mint_amount: u64) -> Instruction {
+    mint_tokens_capped(sale, user, sale_phase, mint_amount, u64::MAX)
+}
+
+pub fn mint_tokens_capped(sale: &Sale, user: &Pubkey, sale_phase: Option<Pubkey>, mint_amount: u64, max_total_cost: u64) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: mint_tokens_accounts(sale, user, sale_phase).to_account_metas(None),
+        data: nsd_minting::instruction::MintTokens { mint_amount, max_total_cost }.data(),
+    }
+}
+
+/// `mint_tokens_v2` and `presale_mint` accounts for `user`, who also pays for the mint
+pub fn mint_tokens_v2_accounts(sale: &Sale, user: &Pubkey, sale_phase: Option<Pubkey>) -> nsd_minting::accounts::MintTokensV2 {
+    nsd_minting::accounts::MintTokensV2 {
+        fee_payer: *user,
+        config: sale.config,
+        treasury: sale.treasury,
+        token_mint: sale.mint(),
+        user: *user,
+        user_account: sale.user_account(user),
+        user_token_account: sale.user_token_account(user),
+        mint_authority: sale.mint_authority,
+        token_program: sale.token_program,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: system_program::ID,
+        sale_phase,
+    }
+}
+
+/// `mint_tokens_v2` without a cost limit
+pub fn mint_tokens_v2(sale: &Sale, user: &Pubkey, sale_phase: Option<Pubkey>, mint_amount: u64) -> Instruction {
+    mint_tokens_v2_capped(sale, user, sale_phase, mint_amount, u64::MAX)
+}
+
+pub fn mint_tokens_v2_capped(sale: &Sale, user: &Pubkey, sale_phase: Option<Pubkey>, mint_amount: u64, max_total_cost: u64) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: mint_tokens_v2_accounts(sale, user, sale_phase).to_account_metas(None),
+        data: nsd_minting::instruction::MintTokensV2 { mint_amount, max_total_cost }.data(),
+    }
+}
+
+pub fn presale_mint(
+    sale: &Sale,
+    user: &Pubkey,
+    sale_phase: Option<Pubkey>,
+    mint_amount: u64,
+    allocation: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: mint_tokens_v2_accounts(sale, user, sale_phase).to_account_metas(None),
+        data: nsd_minting::instruction::PresaleMint { mint_amount, max_total_cost: u64::MAX, allocation, proof }.data(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_sale_phase(
+    context: &ProgramTestContext,
+    sale: &Sale,
+    phase_id: u8,
+    start_timestamp: i64,
+    end_timestamp: i64,
+    price: u64,
+    max_per_wallet: Option<u64>,
+    allowlist_root: Option<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::CreateSalePhase {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            sale_phase: sale.sale_phase(phase_id),
+            admin: context.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::CreateSalePhase {
+            phase_id,
+            start_timestamp,
+            end_timestamp,
+            price,
+            max_per_wallet,
+            allowlist_root,
+        }
+        .data(),
+    }
+}
+
+pub fn update_sale_phase(
+    context: &ProgramTestContext,
+    sale: &Sale,
+    phase_id: u8,
+    start_timestamp: Option<i64>,
+    end_timestamp: Option<i64>,
+    price: Option<u64>,
+    allowlist_root: Option<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::UpdateSalePhase {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            sale_phase: sale.sale_phase(phase_id),
+            admin: context.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::UpdateSalePhase {
+            start_timestamp,
+            end_timestamp,
+            price,
+            max_per_wallet: None,
+            allowlist_root,
+        }
+        .data(),
+    }
+}
+
+/// Append the sale phases `phase_ids` as remaining accounts, for the overlap check of
+/// `create_sale_phase` and `update_sale_phase`
+pub fn with_phases(mut instruction: Instruction, sale: &Sale, phase_ids: &[u8]) -> Instruction {
+    instruction.accounts.extend(phase_ids.iter().map(|phase_id| AccountMeta::new_readonly(sale.sale_phase(*phase_id), false)));
+    instruction
+}
+
+pub fn close_sale_phase(context: &ProgramTestContext, sale: &Sale, phase_id: u8) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::CloseSalePhase {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            sale_phase: sale.sale_phase(phase_id),
+            admin: context.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::CloseSalePhase {}.data(),
+    }
+}
+
+pub fn propose_admin(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey, new_admin: Pubkey) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::ProposeAdmin {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::ProposeAdmin { new_admin }.data(),
+    }
+}
+
+pub fn accept_admin(context: &ProgramTestContext, sale: &Sale, new_admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::AcceptAdmin {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            new_admin: *new_admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::AcceptAdmin {}.data(),
+    }
+}
+
+pub fn cancel_admin_transfer(context: &ProgramTestContext, sale: &Sale, admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::CancelAdminTransfer {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::CancelAdminTransfer {}.data(),
+    }
+}
+
+/// Metadata and Metaplex accounts for SPL Token sales, omitted for Token-2022 sales
+fn metadata_accounts(sale: &Sale) -> (Option<Pubkey>, Option<Pubkey>, Option<Pubkey>) {
+    if sale.token_program == anchor_spl::token::ID {
+        (Some(sale.metadata), Some(sale.metaplex_metadata), Some(mpl_token_metadata::ID))
+    } else {
+        (None, None, None)
+    }
+}
+
+pub fn set_token_metadata(context: &ProgramTestContext, sale: &Sale, name: &str, symbol: &str, uri: &str) -> Instruction {
+    let (metadata, metaplex_metadata, token_metadata_program) = metadata_accounts(sale);
+    set_token_metadata_with(context, sale, metadata, metaplex_metadata, token_metadata_program, name, symbol, uri)
+}
+
+/// `set_token_metadata` with explicit metadata and Metaplex accounts
+#[allow(clippy::too_many_arguments)]
+pub fn set_token_metadata_with(
+    context: &ProgramTestContext,
+    sale: &Sale,
+    metadata: Option<Pubkey>,
+    metaplex_metadata: Option<Pubkey>,
+    token_metadata_program: Option<Pubkey>,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::SetTokenMetadata {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            metadata,
+            admin: context.payer.pubkey(),
+            mint: sale.mint(),
+            mint_authority: sale.mint_authority,
+            metaplex_metadata,
+            token_metadata_program,
+            token_program: sale.token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::SetTokenMetadata {
+            token_mint: sale.mint(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        }
+        .data(),
+    }
+}
+
+pub fn update_token_metadata(
+    context: &ProgramTestContext,
+    sale: &Sale,
+    name: Option<&str>,
+    symbol: Option<&str>,
+    uri: Option<&str>,
+) -> Instruction {
+    let (metadata, metaplex_metadata, token_metadata_program) = metadata_accounts(sale);
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::UpdateTokenMetadata {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            metadata,
+            admin: context.payer.pubkey(),
+            mint_authority: sale.mint_authority,
+            mint: sale.mint(),
+            metaplex_metadata,
+            token_metadata_program,
+            token_program: sale.token_program,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::UpdateTokenMetadata {
+            name: name.map(str::to_string),
+            symbol: symbol.map(str::to_string),
+            uri: uri.map(str::to_string),
+        }
+        .data(),
+    }
+}
+
+pub fn lock_metadata(context: &ProgramTestContext, sale: &Sale) -> Instruction {
+    let (metadata, metaplex_metadata, token_metadata_program) = metadata_accounts(sale);
+    Instruction {
+        program_id: nsd_minting::ID,
+        accounts: nsd_minting::accounts::LockMetadata {
+            fee_payer: context.payer.pubkey(),
+            config: sale.config,
+            metadata,
+            admin: context.payer.pubkey(),
+            mint_authority: sale.mint_authority,
+            mint: sale.mint(),
+            metaplex_metadata,
+            token_metadata_program,
+            token_program: sale.token_program,
+        }
+        .to_account_metas(None),
+        data: nsd_minting::instruction::LockMetadata {}.data(),
+    }
+}
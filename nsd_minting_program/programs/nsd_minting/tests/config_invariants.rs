@@ -0,0 +1,130 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Config invariant tests: the supply floor, the price band and `freeze_max_supply`.
+//! Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use common::*;
+use nsd_minting::{NsdConfig, NsdError, UpdateConfigArgs};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PRICE: u64 = 500;
+const MIN_PRICE: u64 = 100;
+const MAX_PRICE: u64 = 1_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn max_supply_cannot_drop_below_total_minted() {
+    let mut context = start().await;
+    let payer = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 4);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let args = UpdateConfigArgs { max_supply: Some(3), ..Default::default() };
+    let instruction = update_config(&context, &sale, &payer, args);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MaxSupplyBelowMinted);
+
+    // Capping the supply at exactly what was minted ends the sale
+    let args = UpdateConfigArgs { max_supply: Some(4), ..Default::default() };
+    let instruction = update_config(&context, &sale, &payer, args);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.max_supply, config.total_minted);
+}
+
+#[tokio::test]
+async fn price_band_is_checked_at_initialization() {
+    let mut context = start().await;
+
+    let result = initialize_sale_with_band(&mut context, anchor_spl::token::ID, 10, PRICE, Some(MAX_PRICE), Some(MIN_PRICE)).await;
+    assert_nsd_error(result.map(|_| ()), NsdError::InvalidPriceBand);
+
+    let result = initialize_sale_with_band(&mut context, anchor_spl::token::ID, 10, MIN_PRICE - 1, Some(MIN_PRICE), Some(MAX_PRICE)).await;
+    assert_nsd_error(result.map(|_| ()), NsdError::PriceOutOfBand);
+
+    let sale = initialize_sale_with_band(&mut context, anchor_spl::token::ID, 10, PRICE, Some(MIN_PRICE), Some(MAX_PRICE))
+        .await
+        .unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.min_price, Some(MIN_PRICE));
+    assert_eq!(config.max_price, Some(MAX_PRICE));
+    assert!(!config.max_supply_frozen);
+}
+
+#[tokio::test]
+async fn mint_and_phase_prices_stay_within_the_band() {
+    let mut context = start().await;
+    let payer = context.payer.pubkey();
+    let sale = initialize_sale_with_band(&mut context, anchor_spl::token::ID, 10, PRICE, Some(MIN_PRICE), Some(MAX_PRICE))
+        .await
+        .unwrap();
+
+    for price in [0, MIN_PRICE - 1, MAX_PRICE + 1] {
+        let args = UpdateConfigArgs { mint_price: Some(price), ..Default::default() };
+        let instruction = update_config(&context, &sale, &payer, args);
+        assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PriceOutOfBand);
+    }
+
+    let args = UpdateConfigArgs { mint_price: Some(MAX_PRICE), ..Default::default() };
+    let instruction = update_config(&context, &sale, &payer, args);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.mint_price, MAX_PRICE);
+
+    let start = now(&mut context).await;
+    let instruction = create_sale_phase(&context, &sale, 0, start, start + 100, MAX_PRICE + 1, None, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PriceOutOfBand);
+
+    let instruction = create_sale_phase(&context, &sale, 0, start, start + 100, MIN_PRICE, None, None);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = update_sale_phase(&context, &sale, 0, None, None, Some(MIN_PRICE - 1), None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PriceOutOfBand);
+}
+
+#[tokio::test]
+async fn frozen_max_supply_can_only_go_down() {
+    let mut context = start().await;
+    let payer = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+
+    let outsider = Keypair::new();
+    let instruction = freeze_max_supply(&context, &sale, &outsider.pubkey());
+    assert_nsd_error(send(&mut context, instruction, &[&outsider]).await, NsdError::Unauthorized);
+
+    let instruction = freeze_max_supply(&context, &sale, &payer);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert!(config.max_supply_frozen);
+
+    let args = UpdateConfigArgs { max_supply: Some(11), ..Default::default() };
+    let instruction = update_config(&context, &sale, &payer, args);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MaxSupplyFrozen);
+
+    let args = UpdateConfigArgs { max_supply: Some(8), ..Default::default() };
+    let instruction = update_config(&context, &sale, &payer, args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    // Lowering the cap does not let it be raised back
+    let args = UpdateConfigArgs { max_supply: Some(10), ..Default::default() };
+    let instruction = update_config(&context, &sale, &payer, args);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MaxSupplyFrozen);
+
+    // Freezing again keeps the flag set
+    let instruction = freeze_max_supply(&context, &sale, &payer);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert!(config.max_supply_frozen);
+    assert_eq!(config.max_supply, 8);
+}
@@ -0,0 +1,128 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//!
+//! Dutch auction tests: `set_dutch_auction`, time-priced mints and the `current_price` and `quote_mint` views.
+//! Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use common::*;
+use nsd_minting::{BondingCurve, DutchAuction, NsdError, PriceTier};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PRICE: u64 = 1_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn price_decays_from_start_to_floor() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let current = now(&mut context).await;
+    let auction = DutchAuction { start_price: 10_000, floor_price: 1_000, start_timestamp: current + 100, decay_interval: 900 };
+    let instruction = set_dutch_auction(&context, &sale, &admin, Some(auction));
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    // Full price until the auction starts
+    assert_eq!(simulate_u64(&mut context, current_price(&sale, None)).await.unwrap(), 10_000);
+
+    // A third of the way down: 10000 - 9000 * 300 / 900
+    set_time(&mut context, current + 400).await;
+    assert_eq!(simulate_u64(&mut context, current_price(&sale, None)).await.unwrap(), 7_000);
+    assert_eq!(simulate_u64(&mut context, quote_mint(&sale, None, 2)).await.unwrap(), 14_000);
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let treasury_before = balance(&mut context, sale.treasury).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, sale.treasury).await - treasury_before, 14_000);
+
+    // The floor holds once the decay is over
+    set_time(&mut context, current + 5_000).await;
+    assert_eq!(simulate_u64(&mut context, current_price(&sale, None)).await.unwrap(), 1_000);
+    let instruction = mint_tokens(&sale, &buyer.pubkey(), None, 1);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, sale.treasury).await - treasury_before, 15_000);
+}
+
+#[tokio::test]
+async fn price_views_follow_every_pricing_mode() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    assert_eq!(simulate_u64(&mut context, current_price(&sale, None)).await.unwrap(), PRICE);
+    assert_eq!(simulate_u64(&mut context, quote_mint(&sale, None, 3)).await.unwrap(), 3 * PRICE);
+
+    // Token 0 on the curve costs the base price, the quote integrates the curve
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(BondingCurve { base_price: 500, slope: 50 }));
+    send(&mut context, instruction, &[]).await.unwrap();
+    assert_eq!(simulate_u64(&mut context, current_price(&sale, None)).await.unwrap(), 500);
+    assert_eq!(simulate_u64(&mut context, quote_mint(&sale, None, 3)).await.unwrap(), 500 + 550 + 600);
+    let instruction = set_bonding_curve(&context, &sale, &admin, None);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 6);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // 6 tokens sold, so the second tier applies, and the quote walks into the third
+    let tiers = vec![
+        PriceTier { start_supply: 0, price: 100 },
+        PriceTier { start_supply: 5, price: 200 },
+        PriceTier { start_supply: 8, price: 400 },
+    ];
+    let instruction = set_price_tiers(&context, &sale, &admin, tiers);
+    send(&mut context, instruction, &[]).await.unwrap();
+    assert_eq!(simulate_u64(&mut context, current_price(&sale, None)).await.unwrap(), 200);
+    assert_eq!(simulate_u64(&mut context, quote_mint(&sale, None, 3)).await.unwrap(), 200 + 200 + 400);
+
+    // Once phases exist the view needs the active one, like a mint
+    let instruction = set_price_tiers(&context, &sale, &admin, vec![]);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let current = now(&mut context).await;
+    let instruction = create_sale_phase(&context, &sale, 0, current + 100, current + 200, 300, None, None);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let result = simulate_u64(&mut context, current_price(&sale, None)).await;
+    assert_nsd_error(result.map(|_| ()), NsdError::NoActiveSalePhase);
+    set_time(&mut context, current + 150).await;
+    let result = simulate_u64(&mut context, current_price(&sale, Some(sale.sale_phase(0)))).await;
+    assert_eq!(result.unwrap(), 300);
+    let result = simulate_u64(&mut context, quote_mint(&sale, Some(sale.sale_phase(0)), 2)).await;
+    assert_eq!(result.unwrap(), 600);
+}
+
+#[tokio::test]
+async fn auction_is_validated() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale_with_band(&mut context, anchor_spl::token::ID, 100, PRICE, Some(500), Some(5_000))
+        .await
+        .unwrap();
+    let current = now(&mut context).await;
+    let auction = DutchAuction { start_price: 5_000, floor_price: 500, start_timestamp: current, decay_interval: 60 };
+
+    let inverted = DutchAuction { start_price: 500, floor_price: 5_000, ..auction };
+    let instruction = set_dutch_auction(&context, &sale, &admin, Some(inverted));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidDutchAuction);
+
+    let above_band = DutchAuction { start_price: 5_001, ..auction };
+    let instruction = set_dutch_auction(&context, &sale, &admin, Some(above_band));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PriceOutOfBand);
+
+    let outsider = Keypair::new();
+    let instruction = set_dutch_auction(&context, &sale, &outsider.pubkey(), Some(auction));
+    assert_nsd_error(send(&mut context, instruction, &[&outsider]).await, NsdError::Unauthorized);
+
+    let instruction = set_dutch_auction(&context, &sale, &admin, Some(auction));
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(BondingCurve { base_price: 500, slope: 0 }));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PricingModeConflict);
+}
@@ -0,0 +1,150 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Finalize tests: `finalize_sale` revoking the mint authority, sweeping the treasury above the curve reserve
+//! and closing every mint and config update. Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use anchor_spl::token_interface::Mint;
+use common::*;
+use nsd_minting::{BondingCurve, NsdConfig, NsdError, UpdateConfigArgs};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PRICE: u64 = 1_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn finalize_revokes_the_mint_authority_and_closes_the_sale() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 3);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let treasury_before = balance(&mut context, sale.treasury).await;
+
+    let instruction = finalize_sale(&context, &sale, &admin, None);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let mint: Mint = fetch(&mut context, sale.mint()).await;
+    assert_eq!(mint.mint_authority, None.into());
+    assert_eq!(mint.supply, 3);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert!(config.is_finalized);
+    assert!(!config.is_active);
+    assert_eq!(config.total_withdrawn, 0);
+    assert_eq!(balance(&mut context, sale.treasury).await, treasury_before);
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::SaleFinalized);
+    let instruction = mint_tokens(&sale, &buyer.pubkey(), None, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::SaleFinalized);
+
+    let args = UpdateConfigArgs { is_active: Some(true), ..Default::default() };
+    let instruction = update_config(&context, &sale, &admin, args);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::SaleFinalized);
+    let current = now(&mut context).await;
+    let instruction = create_sale_phase(&context, &sale, 0, current, current + 100, PRICE, None, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::SaleFinalized);
+
+    let instruction = finalize_sale(&context, &sale, &admin, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::SaleFinalized);
+}
+
+#[tokio::test]
+async fn sale_phases_can_be_closed_after_finalization() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let current = now(&mut context).await;
+    let instruction = create_sale_phase(&context, &sale, 0, current, current + 100, PRICE, None, None);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = finalize_sale(&context, &sale, &admin, None);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = update_sale_phase(&context, &sale, 0, None, Some(current + 200), None, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::SaleFinalized);
+    let instruction = close_sale_phase(&context, &sale, 0);
+    send(&mut context, instruction, &[]).await.unwrap();
+    assert!(context.banks_client.get_account(sale.sale_phase(0)).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn finalize_can_sweep_the_treasury() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 4);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // Pre-funded so the destination is rent-exempt whatever it receives
+    let destination = funded_keypair(&mut context, BUYER_LAMPORTS).await.pubkey();
+    let treasury_before = balance(&mut context, sale.treasury).await;
+
+    let instruction = finalize_sale(&context, &sale, &admin, Some(destination));
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    assert_eq!(balance(&mut context, destination).await, BUYER_LAMPORTS + 4 * PRICE);
+    assert_eq!(balance(&mut context, sale.treasury).await, treasury_before - 4 * PRICE);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_withdrawn, 4 * PRICE);
+    let mint: Mint = fetch(&mut context, sale.mint()).await;
+    assert_eq!(mint.mint_authority, None.into());
+}
+
+#[tokio::test]
+async fn finalize_keeps_the_curve_reserve_for_sells() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(BondingCurve { base_price: 1_000, slope: 100 }));
+    send(&mut context, instruction, &[]).await.unwrap();
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 5);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // The 6000 lamports paid along the curve are all reserve, nothing is swept
+    let destination = funded_keypair(&mut context, BUYER_LAMPORTS).await.pubkey();
+    let instruction = finalize_sale(&context, &sale, &admin, Some(destination));
+    send(&mut context, instruction, &[]).await.unwrap();
+    assert_eq!(balance(&mut context, destination).await, BUYER_LAMPORTS);
+
+    // Holders still sell back after finalization
+    let seller_before = balance(&mut context, buyer.pubkey()).await;
+    let instruction = sell_tokens(&sale, &buyer.pubkey(), 5, 6_000);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, buyer.pubkey()).await - seller_before, 6_000);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_minted, 0);
+}
+
+#[tokio::test]
+async fn finalize_requires_the_admin_and_a_bound_mint_authority() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+
+    let outsider = Keypair::new();
+    let instruction = finalize_sale(&context, &sale, &outsider.pubkey(), None);
+    assert_nsd_error(send(&mut context, instruction, &[&outsider]).await, NsdError::Unauthorized);
+
+    // An external mint whose authority was never handed to the program
+    let token_mint = create_external_mint(&mut context, &admin, 0).await;
+    let external = Sale::new(token_mint, anchor_spl::token::ID);
+    let instruction = initialize_config(&context, &external, 10, PRICE);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = finalize_sale(&context, &external, &admin, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MintAuthorityNotBound);
+}
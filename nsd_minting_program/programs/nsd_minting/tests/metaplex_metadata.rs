@@ -0,0 +1,81 @@
+//! NSD Minting Program - Solana Smart Contract
+//! 
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Metaplex metadata tests. The Token Metadata program is loaded from
+//! `tests/fixtures/mpl_token_metadata.so`, fetched by `scripts/fetch-fixtures.sh`.
+//! Run with `cargo test-sbf`; the tests fail when the dump is missing.
+
+// Import required modules and macros
+mod common;
+
+use anchor_spl::metadata::mpl_token_metadata;
+use common::*;
+use nsd_minting::{NsdError, NsdTokenMetadata};
+use solana_program_test::{tokio, ProgramTestContext};
+
+async fn fetch_metaplex(context: &mut ProgramTestContext, sale: &Sale) -> mpl_token_metadata::accounts::Metadata {
+    let account = context.banks_client.get_account(sale.metaplex_metadata).await.unwrap().unwrap();
+    assert_eq!(account.owner, mpl_token_metadata::ID);
+    mpl_token_metadata::accounts::Metadata::from_bytes(&account.data).unwrap()
+}
+
+/// Metaplex pads strings with trailing null bytes
+fn trimmed(value: &str) -> &str {
+    value.trim_end_matches('\0')
+}
+
+#[tokio::test]
+async fn set_token_metadata_creates_metaplex_metadata() {
+    let mut context = start_with_metaplex().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 1_000, 0).await;
+
+    let instruction = set_token_metadata(&context, &sale, "NSD", "NSD", "https://example.com/nsd.json");
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let metaplex = fetch_metaplex(&mut context, &sale).await;
+    assert_eq!(metaplex.mint, sale.mint());
+    assert_eq!(metaplex.update_authority, sale.mint_authority);
+    assert_eq!(trimmed(&metaplex.name), "NSD");
+    assert_eq!(trimmed(&metaplex.symbol), "NSD");
+    assert_eq!(trimmed(&metaplex.uri), "https://example.com/nsd.json");
+    assert!(metaplex.is_mutable);
+
+    let nsd: NsdTokenMetadata = fetch(&mut context, sale.metadata).await;
+    assert_eq!(nsd.name, "NSD");
+    assert_eq!(nsd.uri, "https://example.com/nsd.json");
+}
+
+#[tokio::test]
+async fn update_and_lock_keep_metaplex_metadata_in_sync() {
+    let mut context = start_with_metaplex().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 1_000, 0).await;
+
+    let instruction = set_token_metadata(&context, &sale, "NSD", "NSD", "https://example.com/nsd.json");
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = update_token_metadata(&context, &sale, None, None, Some("https://example.com/nsd-v2.json"));
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let metaplex = fetch_metaplex(&mut context, &sale).await;
+    assert_eq!(trimmed(&metaplex.name), "NSD");
+    assert_eq!(trimmed(&metaplex.uri), "https://example.com/nsd-v2.json");
+    let nsd: NsdTokenMetadata = fetch(&mut context, sale.metadata).await;
+    assert_eq!(nsd.uri, "https://example.com/nsd-v2.json");
+
+    let instruction = lock_metadata(&context, &sale);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let metaplex = fetch_metaplex(&mut context, &sale).await;
+    assert!(!metaplex.is_mutable);
+    let nsd: NsdTokenMetadata = fetch(&mut context, sale.metadata).await;
+    assert!(nsd.is_locked);
+
+    let instruction = update_token_metadata(&context, &sale, None, None, Some("https://example.com/nsd-v3.json"));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MetadataLocked);
+}
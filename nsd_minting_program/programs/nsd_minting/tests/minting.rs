@@ -0,0 +1,297 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Minting tests: `initialize_sale`, `mint_tokens`, `mint_tokens_v2`, the sale limits and the cost limit.
+//! Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use common::*;
+use nsd_minting::{NsdConfig, NsdError, NsdUser, UpdateConfigArgs};
+use solana_program_test::tokio;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+
+const PRICE: u64 = 1_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn initialize_sale_creates_mint_owned_by_program_pdas() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+
+    let mint: Mint = fetch(&mut context, sale.mint()).await;
+    assert_eq!(mint.mint_authority, Some(sale.mint_authority).into());
+    assert_eq!(mint.freeze_authority, Some(sale.freeze_authority).into());
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.admin, context.payer.pubkey());
+    assert_eq!(config.token_mint, sale.mint());
+    assert_eq!(config.max_supply, 10);
+    assert_eq!(config.mint_price, PRICE);
+    assert!(config.is_active);
+    assert!(config.mint_authority_bound);
+}
+
+#[tokio::test]
+async fn mint_tokens_v2_pays_treasury_and_records_the_mint() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let treasury_before = balance(&mut context, sale.treasury).await;
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 4);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let token_account: TokenAccount = fetch(&mut context, sale.user_token_account(&buyer.pubkey())).await;
+    assert_eq!(token_account.amount, 4);
+    assert_eq!(balance(&mut context, sale.treasury).await, treasury_before + 4 * PRICE);
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_minted, 4);
+    assert_eq!(config.total_revenue, 4 * PRICE);
+    let user: NsdUser = fetch(&mut context, sale.user_account(&buyer.pubkey())).await;
+    assert_eq!(user.user, buyer.pubkey());
+    assert_eq!(user.tokens_minted, 4);
+}
+
+#[tokio::test]
+async fn mint_tokens_uses_the_legacy_layout() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let instruction = mint_tokens(&sale, &buyer.pubkey(), None, 3);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let token_account: TokenAccount = fetch(&mut context, sale.user_token_account(&buyer.pubkey())).await;
+    assert_eq!(token_account.amount, 3);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_minted, 3);
+}
+
+#[tokio::test]
+async fn mint_tokens_v2_supports_token_2022_mints() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let mint = context.banks_client.get_account(sale.mint()).await.unwrap().unwrap();
+    assert_eq!(mint.owner, anchor_spl::token_2022::ID);
+    let token_account: TokenAccount = fetch(&mut context, sale.user_token_account(&buyer.pubkey())).await;
+    assert_eq!(token_account.amount, 2);
+}
+
+#[tokio::test]
+async fn mint_fails_while_inactive() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let args = UpdateConfigArgs { is_active: Some(false), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::MintingNotActive);
+}
+
+#[tokio::test]
+async fn mint_fails_past_max_supply() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 10);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ExceedsMaxSupply);
+}
+
+#[tokio::test]
+async fn mint_fails_with_insufficient_funds() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, 1_000_000_000).await;
+    // Enough for the token and user account rent, not for the mint price
+    let buyer = funded_keypair(&mut context, 10_000_000).await;
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn mint_fails_on_supply_overflow() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, u64::MAX, 0).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, u64::MAX);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ArithmeticOverflow);
+}
+
+#[tokio::test]
+async fn mint_fails_with_another_sales_mint() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let other = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    // Pair the first sale's config with the other sale's mint
+    let accounts = nsd_minting::accounts::MintTokensV2 {
+        token_mint: other.mint(),
+        user_token_account: other.user_token_account(&buyer.pubkey()),
+        ..mint_tokens_v2_accounts(&sale, &buyer.pubkey(), None)
+    };
+    let instruction = Instruction {
+        program_id: nsd_minting::ID,
+        accounts: accounts.to_account_metas(None),
+        data: nsd_minting::instruction::MintTokensV2 { mint_amount: 1, max_total_cost: PRICE }.data(),
+    };
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::InvalidTokenMint);
+}
+
+#[tokio::test]
+async fn mint_fails_to_another_wallets_token_account() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let other = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let instruction = mint_tokens_v2(&sale, &other.pubkey(), None, 1);
+    send(&mut context, instruction, &[&other]).await.unwrap();
+
+    // Direct the buyer's mint into the other wallet's token account
+    let accounts = nsd_minting::accounts::MintTokens {
+        assoc_token_account: sale.user_token_account(&other.pubkey()),
+        wallet: other.pubkey(),
+        ..mint_tokens_accounts(&sale, &buyer.pubkey(), None)
+    };
+    let instruction = Instruction {
+        program_id: nsd_minting::ID,
+        accounts: accounts.to_account_metas(None),
+        data: nsd_minting::instruction::MintTokens { mint_amount: 1, max_total_cost: PRICE }.data(),
+    };
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::InvalidRecipient);
+
+    // The validated layout checks the recipient before creating or reading the token account,
+    // whether it already exists or not
+    for recipient in [other.pubkey(), Keypair::new().pubkey()] {
+        let accounts = nsd_minting::accounts::MintTokensV2 {
+            user_token_account: sale.user_token_account(&recipient),
+            ..mint_tokens_v2_accounts(&sale, &buyer.pubkey(), None)
+        };
+        let instruction = Instruction {
+            program_id: nsd_minting::ID,
+            accounts: accounts.to_account_metas(None),
+            data: nsd_minting::instruction::MintTokensV2 { mint_amount: 1, max_total_cost: PRICE }.data(),
+        };
+        assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::InvalidRecipient);
+    }
+}
+
+#[tokio::test]
+async fn mint_fails_past_transaction_limit() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let args = UpdateConfigArgs { max_per_transaction: Some(2), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 3);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ExceedsTransactionLimit);
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // A limit of zero removes the limit rather than blocking every mint
+    let args = UpdateConfigArgs { max_per_transaction: Some(0), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.max_per_transaction, u64::MAX);
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 3);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+}
+
+#[tokio::test]
+async fn mint_fails_past_wallet_limit() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let args = UpdateConfigArgs { max_per_wallet: Some(3), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ExceedsWalletLimit);
+}
+
+#[tokio::test]
+async fn mint_fails_during_cooldown() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    let args = UpdateConfigArgs { cooldown_seconds: Some(60), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::MintCooldownActive);
+
+    let later = now(&mut context).await + 60;
+    set_time(&mut context, later).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+}
+
+#[tokio::test]
+async fn mint_fails_when_the_price_rises_past_the_limit() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 10, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+
+    // The buyer quoted 2 tokens at PRICE, then the admin raised the price
+    let args = UpdateConfigArgs { mint_price: Some(2 * PRICE), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = mint_tokens_capped(&sale, &buyer.pubkey(), None, 2, 2 * PRICE);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::PriceExceedsLimit);
+    let instruction = mint_tokens_v2_capped(&sale, &buyer.pubkey(), None, 2, 2 * PRICE);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::PriceExceedsLimit);
+
+    // A limit at or above the new cost goes through
+    let instruction = mint_tokens_v2_capped(&sale, &buyer.pubkey(), None, 2, 4 * PRICE);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_revenue, 4 * PRICE);
+}
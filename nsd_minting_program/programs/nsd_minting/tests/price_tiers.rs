@@ -0,0 +1,99 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//!
+//! Price tier tests: `set_price_tiers`, mints crossing tier boundaries and the tier checks.
+//! Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use common::*;
+use nsd_minting::{NsdConfig, NsdError, PriceTier, MAX_PRICE_TIERS};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PRICE: u64 = 1_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+fn tier(start_supply: u64, price: u64) -> PriceTier {
+    PriceTier { start_supply, price }
+}
+
+#[tokio::test]
+async fn mints_are_charged_per_tier() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let tiers = vec![tier(0, 100), tier(5, 200), tier(10, 300)];
+    let instruction = set_price_tiers(&context, &sale, &admin, tiers.clone());
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.price_tiers, tiers);
+    assert_eq!(config.quote(None, 7, 0).unwrap(), 5 * 100 + 2 * 200);
+
+    // 5 at 100, then 2 at 200
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let treasury_before = balance(&mut context, sale.treasury).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 7);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, sale.treasury).await - treasury_before, 900);
+
+    // 3 at 200, then 7 at 300 in the open-ended last tier
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 10);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, sale.treasury).await - treasury_before, 900 + 2_700);
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_revenue, 3_600);
+
+    // Clearing the tiers restores the flat mint price
+    let instruction = set_price_tiers(&context, &sale, &admin, vec![]);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    assert_eq!(balance(&mut context, sale.treasury).await - treasury_before, 3_600 + PRICE);
+}
+
+#[tokio::test]
+async fn tiers_must_start_at_zero_and_ascend() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+
+    let instruction = set_price_tiers(&context, &sale, &admin, vec![tier(1, 100)]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidPriceTiers);
+
+    let instruction = set_price_tiers(&context, &sale, &admin, vec![tier(0, 100), tier(5, 200), tier(5, 300)]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidPriceTiers);
+
+    let tiers = (0..=MAX_PRICE_TIERS as u64).map(|index| tier(index * 10, PRICE)).collect();
+    let instruction = set_price_tiers(&context, &sale, &admin, tiers);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::TooManyPriceTiers);
+
+    let outsider = Keypair::new();
+    let instruction = set_price_tiers(&context, &sale, &outsider.pubkey(), vec![tier(0, 100)]);
+    assert_nsd_error(send(&mut context, instruction, &[&outsider]).await, NsdError::Unauthorized);
+}
+
+#[tokio::test]
+async fn tier_prices_stay_within_the_band() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale_with_band(&mut context, anchor_spl::token::ID, 100, 500, Some(100), Some(1_000))
+        .await
+        .unwrap();
+
+    let instruction = set_price_tiers(&context, &sale, &admin, vec![tier(0, 100), tier(10, 1_001)]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::PriceOutOfBand);
+
+    let instruction = set_price_tiers(&context, &sale, &admin, vec![tier(0, 100), tier(10, 1_000)]);
+    send(&mut context, instruction, &[]).await.unwrap();
+}
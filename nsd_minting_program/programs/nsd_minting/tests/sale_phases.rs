@@ -0,0 +1,185 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Sale phase and presale tests: `create_sale_phase`, `update_sale_phase`, `close_sale_phase`
+//! and `presale_mint`. Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use common::*;
+use nsd_minting::{merkle, NsdConfig, NsdError, SalePhase, UpdateConfigArgs};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+const PRICE: u64 = 1_000;
+const PHASE_PRICE: u64 = 400;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+/// Two-leaf allowlist: `user` with `allocation`, and one other wallet
+fn allowlist(user: &Pubkey, allocation: u64) -> ([u8; 32], Vec<[u8; 32]>) {
+    let leaf = merkle::allowlist_leaf(user, allocation);
+    let sibling = merkle::allowlist_leaf(&Pubkey::new_unique(), 5);
+    (hash_pair(leaf, sibling), vec![sibling])
+}
+
+/// Create phase 0 opening `start_in` seconds from now and lasting 100 seconds
+async fn create_phase(context: &mut ProgramTestContext, sale: &Sale, start_in: i64, allowlist_root: Option<[u8; 32]>) -> i64 {
+    let start = now(context).await + start_in;
+    let instruction = create_sale_phase(context, sale, 0, start, start + 100, PHASE_PRICE, None, allowlist_root);
+    send(context, instruction, &[]).await.unwrap();
+    start
+}
+
+#[tokio::test]
+async fn mints_only_inside_the_phase_window_at_the_phase_price() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let start = create_phase(&mut context, &sale, 1_000, None).await;
+
+    let phase: SalePhase = fetch(&mut context, sale.sale_phase(0)).await;
+    assert_eq!(phase.config, sale.config);
+    assert_eq!(phase.price, PHASE_PRICE);
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.phase_count, 1);
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), Some(sale.sale_phase(0)), 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::NoActiveSalePhase);
+
+    set_time(&mut context, start).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::NoActiveSalePhase);
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), Some(sale.sale_phase(0)), 2);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_revenue, 2 * PHASE_PRICE);
+
+    set_time(&mut context, start + 100).await;
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), Some(sale.sale_phase(0)), 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::NoActiveSalePhase);
+}
+
+#[tokio::test]
+async fn phase_window_must_open_before_it_closes() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let current = now(&mut context).await;
+
+    let instruction = create_sale_phase(&context, &sale, 0, current, current, PHASE_PRICE, None, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidPhaseWindow);
+
+    let start = create_phase(&mut context, &sale, 0, None).await;
+    let instruction = update_sale_phase(&context, &sale, 0, None, Some(start - 1), None, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidPhaseWindow);
+
+    let instruction = update_sale_phase(&context, &sale, 0, None, Some(start + 500), Some(PHASE_PRICE / 2), None);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let phase: SalePhase = fetch(&mut context, sale.sale_phase(0)).await;
+    assert_eq!(phase.end_timestamp, start + 500);
+    assert_eq!(phase.price, PHASE_PRICE / 2);
+}
+
+#[tokio::test]
+async fn phase_windows_never_overlap() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let start = create_phase(&mut context, &sale, 1_000, None).await;
+
+    // Phase 1 must be checked against phase 0
+    let instruction = create_sale_phase(&context, &sale, 1, start + 50, start + 150, PRICE, None, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidPhaseAccounts);
+    let instruction = with_phases(create_sale_phase(&context, &sale, 1, start + 50, start + 150, PRICE, None, None), &sale, &[0]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::OverlappingSalePhases);
+
+    // Windows are half-open, so phase 1 may open as phase 0 closes
+    let instruction = with_phases(create_sale_phase(&context, &sale, 1, start + 100, start + 200, PRICE, None, None), &sale, &[0]);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = with_phases(update_sale_phase(&context, &sale, 1, Some(start + 99), None, None, None), &sale, &[0]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::OverlappingSalePhases);
+    let instruction = with_phases(update_sale_phase(&context, &sale, 1, Some(start + 99), None, None, None), &sale, &[1]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InvalidPhaseAccounts);
+
+    // Changes that keep the window need no other phases
+    let instruction = update_sale_phase(&context, &sale, 1, None, None, Some(PHASE_PRICE), None);
+    send(&mut context, instruction, &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn allowlisted_phase_requires_presale_mint() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let (root, proof) = allowlist(&buyer.pubkey(), 3);
+    create_phase(&mut context, &sale, 0, Some(root)).await;
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), Some(sale.sale_phase(0)), 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::AllowlistRequired);
+
+    let instruction = presale_mint(&sale, &buyer.pubkey(), Some(sale.sale_phase(0)), 1, 3, proof);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    // Clearing the root opens the phase to public mints
+    let instruction = update_sale_phase(&context, &sale, 0, None, None, None, Some([0; 32]));
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), Some(sale.sale_phase(0)), 1);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+}
+
+#[tokio::test]
+async fn closing_the_last_phase_lifts_the_window() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    create_phase(&mut context, &sale, 1_000, None).await;
+
+    let instruction = close_sale_phase(&context, &sale, 0);
+    send(&mut context, instruction, &[]).await.unwrap();
+    assert!(context.banks_client.get_account(sale.sale_phase(0)).await.unwrap().is_none());
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.phase_count, 0);
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 1);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_revenue, PRICE);
+}
+
+#[tokio::test]
+async fn presale_mint_checks_root_proof_and_allocation() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, PRICE).await;
+    let buyer = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let (root, proof) = allowlist(&buyer.pubkey(), 3);
+
+    let instruction = presale_mint(&sale, &buyer.pubkey(), None, 1, 3, proof.clone());
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::PresaleNotActive);
+
+    let args = UpdateConfigArgs { allowlist_root: Some(root), is_active: Some(false), ..Default::default() };
+    let instruction = update_config(&context, &sale, &context.payer.pubkey(), args);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    // The proof commits to the allocation
+    let instruction = presale_mint(&sale, &buyer.pubkey(), None, 1, 4, proof.clone());
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::InvalidMerkleProof);
+
+    let instruction = presale_mint(&sale, &buyer.pubkey(), None, 4, 3, proof.clone());
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ExceedsAllocation);
+
+    // The presale ignores the public active flag
+    let instruction = presale_mint(&sale, &buyer.pubkey(), None, 2, 3, proof.clone());
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let instruction = presale_mint(&sale, &buyer.pubkey(), None, 2, 3, proof);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ExceedsAllocation);
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_minted, 2);
+}
@@ -0,0 +1,127 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//! Token metadata tests: `set_token_metadata`, `update_token_metadata` and `lock_metadata` on
+//! Token-2022 mints, which keep their metadata in the mint only. The Metaplex path is covered by
+//! `metaplex_metadata.rs`. Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+use common::*;
+use nsd_minting::NsdError;
+use solana_program_test::{tokio, ProgramTestContext};
+
+/// Read the token-metadata extension of a Token-2022 mint
+async fn fetch_mint_metadata(context: &mut ProgramTestContext, sale: &Sale) -> TokenMetadata {
+    let account = context.banks_client.get_account(sale.mint()).await.unwrap().unwrap();
+    let mint = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+    mint.get_variable_len_extension::<TokenMetadata>().unwrap()
+}
+
+#[tokio::test]
+async fn set_token_metadata_writes_into_the_mint() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 100, 0).await;
+
+    let instruction = set_token_metadata(&context, &sale, "NSD", "NSD", "https://example.com/nsd.json");
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let metadata = fetch_mint_metadata(&mut context, &sale).await;
+    assert_eq!(metadata.mint, sale.mint());
+    assert_eq!(metadata.update_authority, OptionalNonZeroPubkey(sale.mint_authority));
+    assert_eq!(metadata.name, "NSD");
+    assert_eq!(metadata.symbol, "NSD");
+    assert_eq!(metadata.uri, "https://example.com/nsd.json");
+
+    // No metadata account is created next to the mint
+    assert!(context.banks_client.get_account(sale.metadata).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn update_token_metadata_updates_the_mint() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 100, 0).await;
+    let instruction = set_token_metadata(&context, &sale, "NSD", "NSD", "https://example.com/nsd.json");
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let uri = "https://example.com/metadata/nsd-token-with-a-longer-path.json";
+    let instruction = update_token_metadata(&context, &sale, Some("NSD Token"), None, Some(uri));
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let metadata = fetch_mint_metadata(&mut context, &sale).await;
+    assert_eq!(metadata.name, "NSD Token");
+    assert_eq!(metadata.symbol, "NSD");
+    assert_eq!(metadata.uri, uri);
+}
+
+#[tokio::test]
+async fn metadata_lengths_are_limited() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 100, 0).await;
+    let long_name = "N".repeat(nsd_minting::MAX_NAME_LENGTH + 1);
+    let long_symbol = "S".repeat(nsd_minting::MAX_SYMBOL_LENGTH + 1);
+    let long_uri = "u".repeat(nsd_minting::MAX_URI_LENGTH + 1);
+
+    let instruction = set_token_metadata(&context, &sale, &long_name, "NSD", "https://example.com/nsd.json");
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::NameTooLong);
+
+    let instruction = set_token_metadata(&context, &sale, "NSD", "NSD", "https://example.com/nsd.json");
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = update_token_metadata(&context, &sale, Some(&long_name), None, None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::NameTooLong);
+    let instruction = update_token_metadata(&context, &sale, None, Some(&long_symbol), None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::SymbolTooLong);
+    let instruction = update_token_metadata(&context, &sale, None, None, Some(&long_uri));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::UriTooLong);
+}
+
+#[tokio::test]
+async fn lock_metadata_removes_the_update_authority() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 100, 0).await;
+    let instruction = set_token_metadata(&context, &sale, "NSD", "NSD", "https://example.com/nsd.json");
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let instruction = lock_metadata(&context, &sale);
+    send(&mut context, instruction, &[]).await.unwrap();
+
+    let metadata = fetch_mint_metadata(&mut context, &sale).await;
+    assert_eq!(metadata.update_authority, OptionalNonZeroPubkey::default());
+
+    let instruction = update_token_metadata(&context, &sale, None, Some("NSD2"), None);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MetadataLocked);
+    let instruction = lock_metadata(&context, &sale);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MetadataLocked);
+}
+
+#[tokio::test]
+async fn spl_token_mints_require_metaplex_accounts() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 100, 0).await;
+
+    let instruction = set_token_metadata_with(&context, &sale, None, None, None, "NSD", "NSD", "https://example.com/nsd.json");
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::MissingMetadataAccounts);
+}
+
+#[tokio::test]
+async fn token_2022_mints_reject_the_metadata_account() {
+    let mut context = start().await;
+    let sale = initialize_sale(&mut context, anchor_spl::token_2022::ID, 100, 0).await;
+
+    let instruction = set_token_metadata_with(&context, &sale, Some(sale.metadata), None, None, "NSD", "NSD", "https://example.com/nsd.json");
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::UnexpectedMetadataAccount);
+}
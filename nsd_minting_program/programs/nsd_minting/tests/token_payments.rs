@@ -0,0 +1,206 @@
+//! NSD Minting Program - Solana Smart Contract
+//!
+//! This program manages the minting and configuration of NSD tokens on the Solana blockchain.
+//! It provides functionality for initializing configurations, minting tokens, updating configurations,
+//! and setting token metadata.
+//!
+//! The program uses the Anchor framework for Solana development and follows best practices for
+//! account management, error handling, and security.
+//!
+//!
+//! Token payment tests: `set_payment_mints`, `mint_tokens_with_token` and `withdraw_payment_tokens`,
+//! paying with a locally created stand-in stablecoin. Run with `cargo test-sbf`.
+
+// Import required modules and macros
+mod common;
+
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use common::*;
+use nsd_minting::{BondingCurve, NsdConfig, NsdError, NsdUser, PaymentMint, UpdateConfigArgs};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const PRICE: u64 = 1_000;
+/// 1.5 stablecoin units per NSD token, at 6 decimals
+const USDC_PRICE: u64 = 1_500_000;
+const BUYER_USDC: u64 = 100_000_000;
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+/// A sale accepting a fresh 6-decimal stablecoin, and a buyer holding `BUYER_USDC` of it
+///
+/// Returns the sale, the stablecoin mint, the buyer and the buyer's stablecoin account.
+async fn stablecoin_sale(context: &mut ProgramTestContext) -> (Sale, Pubkey, Keypair, Pubkey) {
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(context, anchor_spl::token::ID, 1_000, PRICE).await;
+    let usdc = create_external_mint(context, &admin, 6).await.pubkey();
+    let instruction = set_payment_mints(context, &sale, &admin, vec![PaymentMint { mint: usdc, price: USDC_PRICE }]);
+    send(context, instruction, &[]).await.unwrap();
+
+    let buyer = funded_keypair(context, BUYER_LAMPORTS).await;
+    let buyer_usdc = fund_token_account(context, &usdc, &buyer.pubkey(), BUYER_USDC).await;
+    (sale, usdc, buyer, buyer_usdc)
+}
+
+#[tokio::test]
+async fn stablecoin_payments_land_in_the_treasury_token_account() {
+    let mut context = start().await;
+    let (sale, usdc, buyer, buyer_usdc) = stablecoin_sale(&mut context).await;
+    let treasury_lamports = balance(&mut context, sale.treasury).await;
+
+    let instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &usdc, None, 10, 10 * USDC_PRICE);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+
+    let buyer_usdc: TokenAccount = fetch(&mut context, buyer_usdc).await;
+    assert_eq!(buyer_usdc.amount, BUYER_USDC - 10 * USDC_PRICE);
+    let treasury_usdc: TokenAccount = fetch(&mut context, sale.treasury_payment_account(&usdc)).await;
+    assert_eq!(treasury_usdc.amount, 10 * USDC_PRICE);
+    assert_eq!(treasury_usdc.owner, sale.treasury);
+
+    let token_account: TokenAccount = fetch(&mut context, sale.user_token_account(&buyer.pubkey())).await;
+    assert_eq!(token_account.amount, 10);
+    let mint: Mint = fetch(&mut context, sale.mint()).await;
+    assert_eq!(mint.supply, 10);
+    let user: NsdUser = fetch(&mut context, sale.user_account(&buyer.pubkey())).await;
+    assert_eq!(user.tokens_minted, 10);
+
+    // Supply is shared with lamport mints, lamport revenue is untouched
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_minted, 10);
+    assert_eq!(config.total_revenue, 0);
+    assert_eq!(balance(&mut context, sale.treasury).await, treasury_lamports);
+
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 5);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.total_minted, 15);
+    assert_eq!(config.total_revenue, 5 * PRICE);
+}
+
+#[tokio::test]
+async fn token_mints_are_checked_against_the_accepted_list_and_limits() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let (sale, usdc, buyer, _) = stablecoin_sale(&mut context).await;
+
+    // A mint that was never registered
+    let other = create_external_mint(&mut context, &admin, 6).await.pubkey();
+    fund_token_account(&mut context, &other, &buyer.pubkey(), BUYER_USDC).await;
+    let instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &other, None, 1, u64::MAX);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::PaymentMintNotAccepted);
+
+    // The price rises past the buyer's quote
+    let instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &usdc, None, 2, 2 * USDC_PRICE - 1);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::PriceExceedsLimit);
+
+    // More than the buyer holds
+    let instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &usdc, None, 100, u64::MAX);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::InsufficientFunds);
+
+    // Clearing the list stops token payments
+    let instruction = set_payment_mints(&context, &sale, &admin, vec![]);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &usdc, None, 1, u64::MAX);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::PaymentMintNotAccepted);
+}
+
+#[tokio::test]
+async fn token_mints_fail_to_another_wallets_token_account() {
+    let mut context = start().await;
+    let (sale, usdc, buyer, _) = stablecoin_sale(&mut context).await;
+    let other = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = mint_tokens_v2(&sale, &other.pubkey(), None, 1);
+    send(&mut context, instruction, &[&other]).await.unwrap();
+
+    // Direct the buyer's mint into the other wallet's token account
+    let mut instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &usdc, None, 1, u64::MAX);
+    let recipient = sale.user_token_account(&buyer.pubkey());
+    for meta in instruction.accounts.iter_mut().filter(|meta| meta.pubkey == recipient) {
+        meta.pubkey = sale.user_token_account(&other.pubkey());
+    }
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::InvalidRecipient);
+}
+
+#[tokio::test]
+async fn token_mints_ignore_the_lamport_price() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let (sale, usdc, buyer, _) = stablecoin_sale(&mut context).await;
+
+    // A lamport price that overflows for any mint of more than one token
+    let args = UpdateConfigArgs { mint_price: Some(u64::MAX), ..Default::default() };
+    let instruction = update_config(&context, &sale, &admin, args);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = mint_tokens_v2(&sale, &buyer.pubkey(), None, 2);
+    assert_nsd_error(send(&mut context, instruction, &[&buyer]).await, NsdError::ArithmeticOverflow);
+
+    let instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &usdc, None, 2, 2 * USDC_PRICE);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let user: NsdUser = fetch(&mut context, sale.user_account(&buyer.pubkey())).await;
+    assert_eq!(user.tokens_minted, 2);
+}
+
+#[tokio::test]
+async fn payment_mint_lists_are_validated() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let sale = initialize_sale(&mut context, anchor_spl::token::ID, 1_000, PRICE).await;
+    let usdc = create_external_mint(&mut context, &admin, 6).await.pubkey();
+    let payment = PaymentMint { mint: usdc, price: USDC_PRICE };
+
+    let instruction = set_payment_mints(&context, &sale, &admin, vec![payment, payment]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::DuplicatePaymentMint);
+
+    let too_many = (0..5).map(|_| PaymentMint { mint: Pubkey::new_unique(), price: USDC_PRICE }).collect();
+    let instruction = set_payment_mints(&context, &sale, &admin, too_many);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::TooManyPaymentMints);
+
+    let outsider = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = set_payment_mints(&context, &sale, &outsider.pubkey(), vec![payment]);
+    assert_nsd_error(send(&mut context, instruction, &[&outsider]).await, NsdError::Unauthorized);
+
+    // Curve refunds are paid in lamports, so the curve and token payments exclude each other
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(BondingCurve { base_price: PRICE, slope: 1 }));
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = set_payment_mints(&context, &sale, &admin, vec![payment]);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::CurveRequiresLamports);
+
+    let instruction = set_bonding_curve(&context, &sale, &admin, None);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = set_payment_mints(&context, &sale, &admin, vec![payment]);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let instruction = set_bonding_curve(&context, &sale, &admin, Some(BondingCurve { base_price: PRICE, slope: 1 }));
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::CurveRequiresLamports);
+
+    let config: NsdConfig = fetch(&mut context, sale.config).await;
+    assert_eq!(config.payment_mints, vec![payment]);
+}
+
+#[tokio::test]
+async fn admin_withdraws_token_payments() {
+    let mut context = start().await;
+    let admin = context.payer.pubkey();
+    let (sale, usdc, buyer, _) = stablecoin_sale(&mut context).await;
+    let instruction = mint_tokens_with_token(&sale, &buyer.pubkey(), &usdc, None, 4, u64::MAX);
+    send(&mut context, instruction, &[&buyer]).await.unwrap();
+    let collected = 4 * USDC_PRICE;
+
+    let destination = fund_token_account(&mut context, &usdc, &admin, 0).await;
+
+    let outsider = funded_keypair(&mut context, BUYER_LAMPORTS).await;
+    let instruction = withdraw_payment_tokens(&context, &sale, &outsider.pubkey(), &usdc, &destination, 1);
+    assert_nsd_error(send(&mut context, instruction, &[&outsider]).await, NsdError::Unauthorized);
+
+    let instruction = withdraw_payment_tokens(&context, &sale, &admin, &usdc, &destination, collected + 1);
+    assert_nsd_error(send(&mut context, instruction, &[]).await, NsdError::InsufficientTreasuryBalance);
+
+    let instruction = withdraw_payment_tokens(&context, &sale, &admin, &usdc, &destination, collected);
+    send(&mut context, instruction, &[]).await.unwrap();
+    let destination_account: TokenAccount = fetch(&mut context, destination).await;
+    assert_eq!(destination_account.amount, collected);
+    let treasury_usdc: TokenAccount = fetch(&mut context, sale.treasury_payment_account(&usdc)).await;
+    assert_eq!(treasury_usdc.amount, 0);
+}
+
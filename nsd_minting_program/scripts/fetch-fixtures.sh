@@ -0,0 +1,12 @@
+#!/usr/bin/env sh
+# Fetch the program dumps loaded by the tests, see the Testing section of the README
+set -e
+cd "$(dirname "$0")/.."
+
+FIXTURES=programs/nsd_minting/tests/fixtures
+mkdir -p "$FIXTURES"
+
+# Metaplex Token Metadata, loaded by the [[test.genesis]] entry in Anchor.toml and by metaplex_metadata.rs
+if [ ! -f "$FIXTURES/mpl_token_metadata.so" ]; then
+    solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s "$FIXTURES/mpl_token_metadata.so"
+fi
@@ -1,22 +1,1237 @@
-
-import { AnchorProvider, BN, setProvider, web3 } from "@coral-xyz/anchor";
-import * as nsdMintingClient from "../app/program_client";
-import chai from "chai";
-import { assert, expect } from "chai";
-import chaiAsPromised from "chai-as-promised";
+import * as anchor from "@coral-xyz/anchor";
+import { AnchorError, BN, type Program, web3 } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { createHash } from "crypto";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
-chai.use(chaiAsPromised);
+import type { NsdMinting } from "../target/types/nsd_minting";
+
+const U64_MAX = new BN("18446744073709551615");
+const TOKEN_METADATA_PROGRAM_ID = new web3.PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+const TOKEN_PROGRAM_ID = anchor.utils.token.TOKEN_PROGRAM_ID;
+const TOKEN_2022_PROGRAM_ID = new web3.PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// Metaplex metadata PDA for a mint
+const metaplexMetadataAddress = (mint: web3.PublicKey): web3.PublicKey =>
+  web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID,
+  )[0];
 
-const programId = new web3.PublicKey("EMxsAQxuGEtb7QHaPYarXGvuNjz3NWX3QGsQmUb9bBgQ");
+// Assert that a transaction fails with the given program error code
+const expectError = async (promise: Promise<unknown>, code: string) => {
+  try {
+    await promise;
+  } catch (err) {
+    assert.instanceOf(err, AnchorError);
+    assert.strictEqual((err as AnchorError).error.errorCode.code, code);
+    return;
+  }
+  assert.fail(`expected ${code}`);
+};
+
+// Allowlist leaf: sha256(user || allocation as u64 little-endian)
+const allowlistLeaf = (user: web3.PublicKey, allocation: BN): Buffer =>
+  createHash("sha256")
+    .update(user.toBuffer())
+    .update(allocation.toArrayLike(Buffer, "le", 8))
+    .digest();
+
+// Parent node: sha256 of the two children in sorted order
+const hashPair = (a: Buffer, b: Buffer): Buffer =>
+  createHash("sha256")
+    .update(Buffer.compare(a, b) <= 0 ? Buffer.concat([a, b]) : Buffer.concat([b, a]))
+    .digest();
 
 describe("nsd_minting tests", () => {
   // Configure the client to use the local cluster
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
+  const program = anchor.workspace.NsdMinting as Program<NsdMinting>;
   const systemWallet = (provider.wallet as NodeWallet).payer;
+  const tokenMint = web3.Keypair.generate();
+
+  const [configPubkey] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("nsd_config"), tokenMint.publicKey.toBuffer()],
+    program.programId,
+  );
+
+  const updateConfig = (args: {
+    maxSupply?: BN;
+    mintPrice?: BN;
+    maxPerWallet?: BN;
+    maxPerTransaction?: BN;
+    cooldownSeconds?: BN;
+    allowlistRoot?: Buffer;
+  }) =>
+    program.methods
+      .updateConfig({
+        maxSupply: args.maxSupply ?? null,
+        mintPrice: args.mintPrice ?? null,
+        isActive: null,
+        maxPerWallet: args.maxPerWallet ?? null,
+        maxPerTransaction: args.maxPerTransaction ?? null,
+        cooldownSeconds: args.cooldownSeconds ?? null,
+        allowlistRoot: args.allowlistRoot ? Array.from(args.allowlistRoot) : null,
+      })
+      .accountsPartial({
+        feePayer: systemWallet.publicKey,
+        config: configPubkey,
+        admin: systemWallet.publicKey,
+      })
+      .rpc();
+
+  const [salePhasePubkey] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("sale_phase"), configPubkey.toBuffer(), Buffer.from([0])],
+    program.programId,
+  );
+
+  const mint = (amount: BN, salePhase: web3.PublicKey | null = null, maxTotalCost: BN = U64_MAX) =>
+    program.methods
+      .mintTokensV2(amount, maxTotalCost)
+      .accountsPartial({
+        feePayer: systemWallet.publicKey,
+        config: configPubkey,
+        tokenMint: tokenMint.publicKey,
+        user: systemWallet.publicKey,
+        userTokenAccount: anchor.utils.token.associatedAddress({
+          mint: tokenMint.publicKey,
+          owner: systemWallet.publicKey,
+        }),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        salePhase,
+      })
+      .rpc();
+
+  const presaleMint = (amount: BN, allocation: BN, proof: Buffer[]) =>
+    program.methods
+      .presaleMint(amount, U64_MAX, allocation, proof.map((node) => Array.from(node)))
+      .accountsPartial({
+        feePayer: systemWallet.publicKey,
+        config: configPubkey,
+        tokenMint: tokenMint.publicKey,
+        user: systemWallet.publicKey,
+        userTokenAccount: anchor.utils.token.associatedAddress({
+          mint: tokenMint.publicKey,
+          owner: systemWallet.publicKey,
+        }),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        salePhase: null,
+      })
+      .rpc();
+
+  // Decode the Anchor events emitted by a confirmed transaction
+  const eventsOf = async (signature: string) => {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx?.meta?.logMessages ?? []));
+  };
+
+  const updateSalePhase = (args: { start?: number; end?: number; price?: BN; maxPerWallet?: BN }) =>
+    program.methods
+      .updateSalePhase(
+        args.start !== undefined ? new BN(args.start) : null,
+        args.end !== undefined ? new BN(args.end) : null,
+        args.price ?? null,
+        args.maxPerWallet ?? null,
+        null,
+      )
+      .accountsPartial({
+        feePayer: systemWallet.publicKey,
+        config: configPubkey,
+        salePhase: salePhasePubkey,
+        admin: systemWallet.publicKey,
+      })
+      .rpc();
+
+  let initializeSignature: string;
+
+  before(async () => {
+    initializeSignature = await program.methods
+      .initializeSale(0, new BN(10), new BN(1_000), null, null)
+      .accounts({
+        feePayer: systemWallet.publicKey,
+        admin: systemWallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([tokenMint])
+      .rpc();
+  });
+
+  it("emits ConfigInitialized", async () => {
+    const [event] = await eventsOf(initializeSignature);
+
+    assert.strictEqual(event.name, "configInitialized");
+    assert.isTrue(event.data.config.equals(configPubkey));
+    assert.isTrue(event.data.tokenMint.equals(tokenMint.publicKey));
+    assert.strictEqual(event.data.maxSupply.toString(), "10");
+    assert.strictEqual(event.data.mintPrice.toString(), "1000");
+  });
+
+  it("mints exactly up to max_supply", async () => {
+    const signature = await mint(new BN(10));
+
+    const config = await program.account.nsdConfig.fetch(configPubkey);
+    assert.strictEqual(config.totalMinted.toString(), "10");
+    assert.strictEqual(config.totalRevenue.toString(), "10000");
+
+    const [event] = await eventsOf(signature);
+    assert.strictEqual(event.name, "tokensMinted");
+    assert.isTrue(event.data.user.equals(systemWallet.publicKey));
+    assert.strictEqual(event.data.amount.toString(), "10");
+    assert.strictEqual(event.data.pricePaid.toString(), "10000");
+    assert.strictEqual(event.data.totalMinted.toString(), "10");
+  });
+
+  it("rejects a mint one past max_supply", async () => {
+    await expectError(mint(new BN(1)), "ExceedsMaxSupply");
+  });
+
+  it("rejects a total cost that overflows u64", async () => {
+    const signature = await updateConfig({ maxSupply: U64_MAX, mintPrice: U64_MAX });
+
+    const [event] = await eventsOf(signature);
+    assert.strictEqual(event.name, "configUpdated");
+    assert.strictEqual(event.data.old.maxSupply.toString(), "10");
+    assert.strictEqual(event.data.old.mintPrice.toString(), "1000");
+    assert.strictEqual(event.data.new.maxSupply.toString(), U64_MAX.toString());
+    assert.strictEqual(event.data.new.mintPrice.toString(), U64_MAX.toString());
+    assert.strictEqual(event.data.old.cooldownSeconds.toString(), event.data.new.cooldownSeconds.toString());
+
+    await expectError(mint(new BN(2)), "ArithmeticOverflow");
+  });
+
+  it("rejects a total supply that overflows u64", async () => {
+    await updateConfig({ mintPrice: new BN(0) });
+
+    await expectError(mint(U64_MAX), "ArithmeticOverflow");
+  });
+
+  it("rejects a mint above max_per_transaction", async () => {
+    await updateConfig({ maxPerTransaction: new BN(5) });
+
+    await expectError(mint(new BN(6)), "ExceedsTransactionLimit");
+  });
+
+  it("rejects a mint past max_per_wallet", async () => {
+    // The wallet already holds 10 tokens from the first test
+    await updateConfig({ maxPerWallet: new BN(12) });
+
+    await expectError(mint(new BN(3)), "ExceedsWalletLimit");
+  });
+
+  it("rejects a mint during the cooldown", async () => {
+    await updateConfig({ maxPerWallet: new BN(0), cooldownSeconds: new BN(3_600) });
+
+    await expectError(mint(new BN(1)), "MintCooldownActive");
+  });
+
+  describe("allowlist presale", () => {
+    // The wallet already holds 10 tokens, so an allocation of 12 leaves room for 2 more
+    const allocation = new BN(12);
+    const leaf = allowlistLeaf(systemWallet.publicKey, allocation);
+    const sibling = allowlistLeaf(web3.Keypair.generate().publicKey, new BN(50));
+    const root = hashPair(leaf, sibling);
+
+    before(async () => {
+      await updateConfig({ cooldownSeconds: new BN(0), allowlistRoot: root });
+    });
+
+    it("rejects a proof for a different allocation", async () => {
+      await expectError(presaleMint(new BN(1), new BN(100), [sibling]), "InvalidMerkleProof");
+    });
+
+    it("rejects a mint past the allocation", async () => {
+      await expectError(presaleMint(new BN(3), allocation, [sibling]), "ExceedsAllocation");
+    });
+
+    it("mints up to the allocation", async () => {
+      await presaleMint(new BN(2), allocation, [sibling]);
+
+      const config = await program.account.nsdConfig.fetch(configPubkey);
+      assert.strictEqual(config.totalMinted.toString(), "12");
+    });
+
+    it("rejects presale mints once the root is cleared", async () => {
+      await updateConfig({ allowlistRoot: Buffer.alloc(32) });
+
+      await expectError(presaleMint(new BN(1), allocation, [sibling]), "PresaleNotActive");
+    });
+  });
+
+  describe("sale phases", () => {
+    const now = Math.floor(Date.now() / 1_000);
+
+    before(async () => {
+      // Phase 0 opens in an hour
+      await program.methods
+        .createSalePhase(0, new BN(now + 3_600), new BN(now + 7_200), new BN(7), null, null)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: configPubkey,
+          admin: systemWallet.publicKey,
+        })
+        .rpc();
+    });
+
+    it("rejects mints outside every phase window", async () => {
+      await expectError(mint(new BN(1)), "NoActiveSalePhase");
+      await expectError(mint(new BN(1), salePhasePubkey), "NoActiveSalePhase");
+    });
+
+    it("rejects a phase that closes before it opens", async () => {
+      await expectError(updateSalePhase({ start: now + 7_200 }), "InvalidPhaseWindow");
+    });
+
+    it("mints at the phase price inside the window", async () => {
+      await updateSalePhase({ start: now - 600 });
+      await mint(new BN(1), salePhasePubkey);
+
+      const config = await program.account.nsdConfig.fetch(configPubkey);
+      assert.strictEqual(config.totalMinted.toString(), "13");
+      assert.strictEqual(config.totalRevenue.toString(), "10007");
+    });
+
+    it("enforces the phase wallet cap", async () => {
+      await updateSalePhase({ maxPerWallet: new BN(13) });
+
+      await expectError(mint(new BN(1), salePhasePubkey), "ExceedsWalletLimit");
+    });
+
+    it("lifts the phase requirement once the last phase is closed", async () => {
+      await program.methods
+        .closeSalePhase()
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: configPubkey,
+          salePhase: salePhasePubkey,
+          admin: systemWallet.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.nsdConfig.fetch(configPubkey);
+      assert.strictEqual(config.phaseCount, 0);
+    });
+  });
+
+  describe("admin handover", () => {
+    const newAdmin = web3.Keypair.generate();
+
+    const proposeAdmin = (admin: web3.Keypair, pendingAdmin: web3.PublicKey) =>
+      program.methods
+        .proposeAdmin(pendingAdmin)
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: configPubkey, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+    const acceptAdmin = (signer: web3.Keypair) =>
+      program.methods
+        .acceptAdmin()
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: configPubkey, newAdmin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    it("rejects accepting without a proposal", async () => {
+      await expectError(acceptAdmin(newAdmin), "NoPendingAdmin");
+    });
+
+    it("rejects a proposal from a non-admin", async () => {
+      await expectError(proposeAdmin(newAdmin, newAdmin.publicKey), "Unauthorized");
+    });
+
+    it("cancels a pending proposal", async () => {
+      await proposeAdmin(systemWallet, newAdmin.publicKey);
+
+      const signature = await program.methods
+        .cancelAdminTransfer()
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: configPubkey, admin: systemWallet.publicKey })
+        .rpc();
+
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "adminTransferCancelled");
+      assert.isTrue(event.data.pendingAdmin.equals(newAdmin.publicKey));
+      await expectError(acceptAdmin(newAdmin), "NoPendingAdmin");
+    });
+
+    it("rejects acceptance by a key other than the pending admin", async () => {
+      const signature = await proposeAdmin(systemWallet, newAdmin.publicKey);
+
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "adminProposed");
+      await expectError(acceptAdmin(web3.Keypair.generate()), "InvalidPendingAdmin");
+    });
+
+    it("hands over to the pending admin and back", async () => {
+      const signature = await acceptAdmin(newAdmin);
+
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "adminTransferred");
+      assert.isTrue(event.data.oldAdmin.equals(systemWallet.publicKey));
+      assert.isTrue(event.data.newAdmin.equals(newAdmin.publicKey));
+
+      let config = await program.account.nsdConfig.fetch(configPubkey);
+      assert.isTrue(config.admin.equals(newAdmin.publicKey));
+      assert.isNull(config.pendingAdmin);
+      await expectError(updateConfig({ mintPrice: new BN(1) }), "Unauthorized");
+
+      await proposeAdmin(newAdmin, systemWallet.publicKey);
+      await acceptAdmin(systemWallet);
+
+      config = await program.account.nsdConfig.fetch(configPubkey);
+      assert.isTrue(config.admin.equals(systemWallet.publicKey));
+    });
+  });
+
+  it("emits MetadataSet", async () => {
+    const signature = await program.methods
+      .setTokenMetadata(tokenMint.publicKey, "NSD", "NSD", "https://example.com/nsd.json")
+      .accountsPartial({
+        feePayer: systemWallet.publicKey,
+        admin: systemWallet.publicKey,
+        mint: tokenMint.publicKey,
+        metaplexMetadata: metaplexMetadataAddress(tokenMint.publicKey),
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const [event] = await eventsOf(signature);
+    assert.strictEqual(event.name, "metadataSet");
+    assert.isTrue(event.data.mint.equals(tokenMint.publicKey));
+    assert.strictEqual(event.data.symbol, "NSD");
+    assert.strictEqual(event.data.uri, "https://example.com/nsd.json");
+  });
+
+  describe("token metadata", () => {
+    // Metadata was set by the "emits MetadataSet" test
+    const [metadataPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), tokenMint.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    const updateMetadata = (name: string | null, symbol: string | null, uri: string | null) =>
+      program.methods
+        .updateTokenMetadata(name, symbol, uri)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: configPubkey,
+          metadata: metadataPubkey,
+          admin: systemWallet.publicKey,
+          mint: tokenMint.publicKey,
+          metaplexMetadata: metaplexMetadataAddress(tokenMint.publicKey),
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    it("updates only the provided fields and resizes the account", async () => {
+      const uri = "https://example.com/metadata/nsd-token-with-a-longer-path.json";
+      await updateMetadata("NSD Token", null, uri);
+
+      const metadata = await program.account.nsdTokenMetadata.fetch(metadataPubkey);
+      assert.strictEqual(metadata.name, "NSD Token");
+      assert.strictEqual(metadata.symbol, "NSD");
+      assert.strictEqual(metadata.uri, uri);
+
+      const info = await provider.connection.getAccountInfo(metadataPubkey);
+      assert.strictEqual(info?.data.length, 8 + 32 + 4 + 9 + 4 + 3 + 4 + uri.length + 1 + 1);
+    });
+
+    it("rejects strings over the maximum lengths", async () => {
+      await expectError(updateMetadata("N".repeat(33), null, null), "NameTooLong");
+      await expectError(updateMetadata(null, "S".repeat(11), null), "SymbolTooLong");
+      await expectError(updateMetadata(null, null, "u".repeat(201)), "UriTooLong");
+    });
+
+    it("rejects updates once locked", async () => {
+      await program.methods
+        .lockMetadata()
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: configPubkey,
+          metadata: metadataPubkey,
+          admin: systemWallet.publicKey,
+          mint: tokenMint.publicKey,
+          metaplexMetadata: metaplexMetadataAddress(tokenMint.publicKey),
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      await expectError(updateMetadata(null, "NSD2", null), "MetadataLocked");
+    });
+  });
+
+  describe("independent sales", () => {
+    const otherMint = web3.Keypair.generate();
+    const [otherConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), otherMint.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    before(async () => {
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(500), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: otherMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([otherMint])
+        .rpc();
+    });
+
+    it("mints from a second sale without touching the first", async () => {
+      const before = await program.account.nsdConfig.fetch(configPubkey);
+
+      await program.methods
+        .mintTokensV2(new BN(4), U64_MAX)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: otherConfigPubkey,
+          tokenMint: otherMint.publicKey,
+          user: systemWallet.publicKey,
+          userTokenAccount: anchor.utils.token.associatedAddress({
+            mint: otherMint.publicKey,
+            owner: systemWallet.publicKey,
+          }),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          salePhase: null,
+        })
+        .rpc();
+
+      const other = await program.account.nsdConfig.fetch(otherConfigPubkey);
+      assert.strictEqual(other.totalMinted.toString(), "4");
+      assert.strictEqual(other.totalRevenue.toString(), "2000");
+
+      const after = await program.account.nsdConfig.fetch(configPubkey);
+      assert.strictEqual(after.totalMinted.toString(), before.totalMinted.toString());
+    });
+
+    it("rejects a config paired with another sale's mint", async () => {
+      await expectError(
+        program.methods
+          .mintTokensV2(new BN(1), U64_MAX)
+          .accountsPartial({
+            feePayer: systemWallet.publicKey,
+            config: otherConfigPubkey,
+            tokenMint: tokenMint.publicKey,
+            user: systemWallet.publicKey,
+            userTokenAccount: anchor.utils.token.associatedAddress({
+              mint: tokenMint.publicKey,
+              owner: systemWallet.publicKey,
+            }),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            salePhase: null,
+          })
+          .rpc(),
+        "InvalidTokenMint",
+      );
+    });
+  });
+
+  describe("config invariants", () => {
+    const bandMint = web3.Keypair.generate();
+    const [bandConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), bandMint.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    const updateBandConfig = (args: { maxSupply?: BN; mintPrice?: BN }) =>
+      program.methods
+        .updateConfig({
+          maxSupply: args.maxSupply ?? null,
+          mintPrice: args.mintPrice ?? null,
+          isActive: null,
+          maxPerWallet: null,
+          maxPerTransaction: null,
+          cooldownSeconds: null,
+          allowlistRoot: null,
+        })
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: bandConfigPubkey, admin: systemWallet.publicKey })
+        .rpc();
+
+    before(async () => {
+      // Prices may only move between 100 and 1000 lamports
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(500), new BN(100), new BN(1_000))
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: bandMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bandMint])
+        .rpc();
+
+      await program.methods
+        .mintTokensV2(new BN(5), U64_MAX)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: bandConfigPubkey,
+          tokenMint: bandMint.publicKey,
+          user: systemWallet.publicKey,
+          userTokenAccount: anchor.utils.token.associatedAddress({
+            mint: bandMint.publicKey,
+            owner: systemWallet.publicKey,
+          }),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          salePhase: null,
+        })
+        .rpc();
+    });
+
+    it("rejects a max_supply below total_minted", async () => {
+      await expectError(updateBandConfig({ maxSupply: new BN(4) }), "MaxSupplyBelowMinted");
+    });
+
+    it("keeps the mint price within the band", async () => {
+      await expectError(updateBandConfig({ mintPrice: new BN(0) }), "PriceOutOfBand");
+      await expectError(updateBandConfig({ mintPrice: new BN(1_001) }), "PriceOutOfBand");
+      await updateBandConfig({ mintPrice: new BN(1_000) });
+
+      const config = await program.account.nsdConfig.fetch(bandConfigPubkey);
+      assert.strictEqual(config.mintPrice.toString(), "1000");
+      assert.strictEqual(config.minPrice?.toString(), "100");
+      assert.strictEqual(config.maxPrice?.toString(), "1000");
+    });
+
+    it("only lowers max_supply once frozen", async () => {
+      const signature = await program.methods
+        .freezeMaxSupply()
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: bandConfigPubkey, admin: systemWallet.publicKey })
+        .rpc();
+
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "maxSupplyFrozen");
+      assert.strictEqual(event.data.maxSupply.toString(), "100");
+
+      await expectError(updateBandConfig({ maxSupply: new BN(101) }), "MaxSupplyFrozen");
+      await updateBandConfig({ maxSupply: new BN(50) });
+
+      const config = await program.account.nsdConfig.fetch(bandConfigPubkey);
+      assert.isTrue(config.maxSupplyFrozen);
+      assert.strictEqual(config.maxSupply.toString(), "50");
+    });
+  });
+
+  describe("finalize sale", () => {
+    const finalMint = web3.Keypair.generate();
+    const [finalConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), finalMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const finalUserTokenAccount = anchor.utils.token.associatedAddress({
+      mint: finalMint.publicKey,
+      owner: systemWallet.publicKey,
+    });
+
+    const mintFinalTokens = (amount: number) =>
+      program.methods
+        .mintTokensV2(new BN(amount), U64_MAX)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: finalConfigPubkey,
+          tokenMint: finalMint.publicKey,
+          user: systemWallet.publicKey,
+          userTokenAccount: finalUserTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          salePhase: null,
+        })
+        .rpc();
+
+    const finalize = () =>
+      program.methods
+        .finalizeSale()
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: finalConfigPubkey,
+          admin: systemWallet.publicKey,
+          tokenMint: finalMint.publicKey,
+          destination: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeSale(0, new BN(10), new BN(1_000), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: finalMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([finalMint])
+        .rpc();
+      await mintFinalTokens(2);
+    });
+
+    it("revokes the mint authority for good", async () => {
+      const signature = await finalize();
+
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "saleFinalized");
+      assert.strictEqual(event.data.totalMinted.toString(), "2");
+      assert.strictEqual(event.data.swept.toString(), "0");
+
+      // SPL Token mint layout: COption<Pubkey> mint authority tag at offset 0
+      const mintInfo = await provider.connection.getAccountInfo(finalMint.publicKey);
+      assert.strictEqual(mintInfo.data.readUInt32LE(0), 0);
+
+      const config = await program.account.nsdConfig.fetch(finalConfigPubkey);
+      assert.isTrue(config.isFinalized);
+      assert.isFalse(config.isActive);
+    });
+
+    it("rejects minting, config updates and a second finalize", async () => {
+      await expectError(mintFinalTokens(1), "SaleFinalized");
+      await expectError(
+        program.methods
+          .updateConfig({
+            maxSupply: null,
+            mintPrice: null,
+            isActive: true,
+            maxPerWallet: null,
+            maxPerTransaction: null,
+            cooldownSeconds: null,
+            allowlistRoot: null,
+          })
+          .accountsPartial({ feePayer: systemWallet.publicKey, config: finalConfigPubkey, admin: systemWallet.publicKey })
+          .rpc(),
+        "SaleFinalized",
+      );
+      await expectError(finalize(), "SaleFinalized");
+    });
+  });
+
+  describe("price tiers", () => {
+    const tieredMint = web3.Keypair.generate();
+    const [tieredConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), tieredMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [tieredTreasuryPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), tieredConfigPubkey.toBuffer()],
+      program.programId,
+    );
+
+    // Same walk as NsdConfig::quote: each token pays the tier covering its position in the supply
+    const quote = (tiers: { startSupply: BN; price: BN }[], totalMinted: BN, amount: BN): BN => {
+      let sold = totalMinted;
+      const end = totalMinted.add(amount);
+      let cost = new BN(0);
+      tiers.forEach((tier, index) => {
+        const tierEnd = index + 1 < tiers.length ? tiers[index + 1].startSupply : end;
+        if (sold.gte(tierEnd) || sold.gte(end)) return;
+        const count = BN.min(end, tierEnd).sub(sold);
+        cost = cost.add(count.mul(tier.price));
+        sold = sold.add(count);
+      });
+      return cost;
+    };
+
+    before(async () => {
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(1_000), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: tieredMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([tieredMint])
+        .rpc();
+    });
+
+    it("charges a mint crossing tier boundaries per tier", async () => {
+      const tiers = [
+        { startSupply: new BN(0), price: new BN(100) },
+        { startSupply: new BN(5), price: new BN(200) },
+      ];
+      const signature = await program.methods
+        .setPriceTiers(tiers)
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: tieredConfigPubkey, admin: systemWallet.publicKey })
+        .rpc();
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "priceTiersSet");
+
+      const config = await program.account.nsdConfig.fetch(tieredConfigPubkey);
+      const expected = quote(config.priceTiers, config.totalMinted, new BN(8));
+      assert.strictEqual(expected.toString(), "1100");
+
+      const treasuryBefore = await provider.connection.getBalance(tieredTreasuryPubkey);
+      await program.methods
+        .mintTokensV2(new BN(8), U64_MAX)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: tieredConfigPubkey,
+          tokenMint: tieredMint.publicKey,
+          user: systemWallet.publicKey,
+          userTokenAccount: anchor.utils.token.associatedAddress({
+            mint: tieredMint.publicKey,
+            owner: systemWallet.publicKey,
+          }),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          salePhase: null,
+        })
+        .rpc();
+      const treasuryAfter = await provider.connection.getBalance(tieredTreasuryPubkey);
+      assert.strictEqual((treasuryAfter - treasuryBefore).toString(), expected.toString());
+    });
+
+    it("rejects tiers that do not start at zero", async () => {
+      await expectError(
+        program.methods
+          .setPriceTiers([{ startSupply: new BN(1), price: new BN(100) }])
+          .accountsPartial({ feePayer: systemWallet.publicKey, config: tieredConfigPubkey, admin: systemWallet.publicKey })
+          .rpc(),
+        "InvalidPriceTiers",
+      );
+    });
+  });
+
+  describe("bonding curve", () => {
+    const curveMint = web3.Keypair.generate();
+    const [curveConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), curveMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const curveUserTokenAccount = anchor.utils.token.associatedAddress({
+      mint: curveMint.publicKey,
+      owner: systemWallet.publicKey,
+    });
+
+    const sell = (amount: number, minLamportsOut: number) =>
+      program.methods
+        .sellTokens(new BN(amount), new BN(minLamportsOut))
+        .accountsPartial({
+          user: systemWallet.publicKey,
+          config: curveConfigPubkey,
+          tokenMint: curveMint.publicKey,
+          userTokenAccount: curveUserTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(1_000), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: curveMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([curveMint])
+        .rpc();
+
+      // Token n costs 1000 + 100n lamports
+      await program.methods
+        .setBondingCurve({ basePrice: new BN(1_000), slope: new BN(100) })
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: curveConfigPubkey, tokenMint: curveMint.publicKey, admin: systemWallet.publicKey })
+        .rpc();
+
+      await program.methods
+        .mintTokensV2(new BN(4), U64_MAX)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: curveConfigPubkey,
+          tokenMint: curveMint.publicKey,
+          user: systemWallet.publicKey,
+          userTokenAccount: curveUserTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          salePhase: null,
+        })
+        .rpc();
+    });
+
+    it("charges the integral of the curve", async () => {
+      const config = await program.account.nsdConfig.fetch(curveConfigPubkey);
+      assert.strictEqual(config.totalMinted.toString(), "4");
+      assert.strictEqual(config.totalRevenue.toString(), "4600");
+    });
+
+    it("refunds sells along the same curve", async () => {
+      await expectError(sell(2, 2_501), "SlippageExceeded");
+
+      const signature = await sell(2, 2_500);
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "tokensSold");
+      assert.strictEqual(event.data.refund.toString(), "2500");
+
+      const config = await program.account.nsdConfig.fetch(curveConfigPubkey);
+      assert.strictEqual(config.totalMinted.toString(), "2");
+      assert.strictEqual(config.totalRefunded.toString(), "2500");
+      const balance = await provider.connection.getTokenAccountBalance(curveUserTokenAccount);
+      assert.strictEqual(balance.value.amount, "2");
+    });
+  });
+
+  describe("dutch auction", () => {
+    const auctionMint = web3.Keypair.generate();
+    const [auctionConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), auctionMint.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    const currentPrice = () =>
+      program.methods.currentPrice().accountsPartial({ config: auctionConfigPubkey, salePhase: null }).view();
+
+    before(async () => {
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(1_000), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: auctionMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([auctionMint])
+        .rpc();
+    });
+
+    it("reads the flat price through the view", async () => {
+      assert.strictEqual((await currentPrice()).toString(), "1000");
+    });
+
+    it("holds the start price until the auction starts", async () => {
+      // Far enough ahead that the local validator clock can't reach it during the test
+      const startTimestamp = Math.floor(Date.now() / 1000) + 3_600;
+      await program.methods
+        .setDutchAuction({
+          startPrice: new BN(50_000),
+          floorPrice: new BN(5_000),
+          startTimestamp: new BN(startTimestamp),
+          decayInterval: new BN(600),
+        })
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: auctionConfigPubkey, admin: systemWallet.publicKey })
+        .rpc();
+
+      assert.strictEqual((await currentPrice()).toString(), "50000");
+      const config = await program.account.nsdConfig.fetch(auctionConfigPubkey);
+      assert.strictEqual(config.dutchAuction?.floorPrice.toString(), "5000");
+    });
+
+    it("drops to the floor once the decay is over", async () => {
+      await program.methods
+        .setDutchAuction({
+          startPrice: new BN(50_000),
+          floorPrice: new BN(5_000),
+          startTimestamp: new BN(0),
+          decayInterval: new BN(600),
+        })
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: auctionConfigPubkey, admin: systemWallet.publicKey })
+        .rpc();
+
+      assert.strictEqual((await currentPrice()).toString(), "5000");
+    });
+
+    it("caps the mint cost at the buyer's quote", async () => {
+      const mintAuction = (maxTotalCost: BN) =>
+        program.methods
+          .mintTokensV2(new BN(2), maxTotalCost)
+          .accountsPartial({
+            feePayer: systemWallet.publicKey,
+            config: auctionConfigPubkey,
+            tokenMint: auctionMint.publicKey,
+            user: systemWallet.publicKey,
+            userTokenAccount: anchor.utils.token.associatedAddress({
+              mint: auctionMint.publicKey,
+              owner: systemWallet.publicKey,
+            }),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            salePhase: null,
+          })
+          .rpc();
+
+      const quote = await program.methods
+        .quoteMint(new BN(2))
+        .accountsPartial({ config: auctionConfigPubkey, salePhase: null })
+        .view();
+      assert.strictEqual(quote.toString(), (await currentPrice()).muln(2).toString());
+      await expectError(mintAuction(quote.subn(1)), "PriceExceedsLimit");
+      await mintAuction(quote);
+
+      const config = await program.account.nsdConfig.fetch(auctionConfigPubkey);
+      assert.strictEqual(config.totalRevenue.toString(), quote.toString());
+    });
+  });
+
+  describe("stablecoin payments", () => {
+    const usdcMint = web3.Keypair.generate();
+    const saleMint = web3.Keypair.generate();
+    const [saleConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), saleMint.publicKey.toBuffer()],
+      program.programId,
+    );
+    const [treasuryPubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), saleConfigPubkey.toBuffer()],
+      program.programId,
+    );
+    const buyerUsdcAccount = anchor.utils.token.associatedAddress({ mint: usdcMint.publicKey, owner: systemWallet.publicKey });
+    const treasuryUsdcAccount = anchor.utils.token.associatedAddress({ mint: usdcMint.publicKey, owner: treasuryPubkey });
+    // 1.5 stand-in USDC per token, at 6 decimals
+    const usdcPrice = new BN(1_500_000);
+
+    const mintWithUsdc = (amount: number, maxTotalCost: BN = U64_MAX) =>
+      program.methods
+        .mintTokensWithToken(new BN(amount), maxTotalCost)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: saleConfigPubkey,
+          tokenMint: saleMint.publicKey,
+          user: systemWallet.publicKey,
+          userTokenAccount: anchor.utils.token.associatedAddress({ mint: saleMint.publicKey, owner: systemWallet.publicKey }),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          paymentMint: usdcMint.publicKey,
+          userPaymentAccount: buyerUsdcAccount,
+          treasuryPaymentAccount: treasuryUsdcAccount,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+          salePhase: null,
+        })
+        .rpc();
+
+    before(async () => {
+      // Stand-in stablecoin built from raw SPL Token instructions: InitializeMint2 (20) and MintTo (7)
+      const mintTo = Buffer.alloc(9);
+      mintTo.writeUInt8(7, 0);
+      mintTo.writeBigUInt64LE(100_000_000n, 1);
+      const transaction = new web3.Transaction().add(
+        web3.SystemProgram.createAccount({
+          fromPubkey: systemWallet.publicKey,
+          newAccountPubkey: usdcMint.publicKey,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(82),
+          space: 82,
+          programId: TOKEN_PROGRAM_ID,
+        }),
+        new web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: [{ pubkey: usdcMint.publicKey, isSigner: false, isWritable: true }],
+          data: Buffer.concat([Buffer.from([20, 6]), systemWallet.publicKey.toBuffer(), Buffer.from([0])]),
+        }),
+        new web3.TransactionInstruction({
+          programId: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          keys: [
+            { pubkey: systemWallet.publicKey, isSigner: true, isWritable: true },
+            { pubkey: buyerUsdcAccount, isSigner: false, isWritable: true },
+            { pubkey: systemWallet.publicKey, isSigner: false, isWritable: false },
+            { pubkey: usdcMint.publicKey, isSigner: false, isWritable: false },
+            { pubkey: web3.SystemProgram.programId, isSigner: false, isWritable: false },
+            { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          ],
+          data: Buffer.alloc(0),
+        }),
+        new web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: [
+            { pubkey: usdcMint.publicKey, isSigner: false, isWritable: true },
+            { pubkey: buyerUsdcAccount, isSigner: false, isWritable: true },
+            { pubkey: systemWallet.publicKey, isSigner: true, isWritable: false },
+          ],
+          data: mintTo,
+        }),
+      );
+      await provider.sendAndConfirm(transaction, [usdcMint]);
+
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(1_000), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: saleMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([saleMint])
+        .rpc();
+    });
+
+    it("rejects payment in a mint that is not accepted", async () => {
+      await expectError(mintWithUsdc(1), "PaymentMintNotAccepted");
+    });
+
+    it("registers the stablecoin and emits PaymentMintsSet", async () => {
+      const signature = await program.methods
+        .setPaymentMints([{ mint: usdcMint.publicKey, price: usdcPrice }])
+        .accountsPartial({ feePayer: systemWallet.publicKey, config: saleConfigPubkey, admin: systemWallet.publicKey })
+        .rpc();
+      const [event] = await eventsOf(signature);
+      assert.strictEqual(event.name, "paymentMintsSet");
+
+      const config = await program.account.nsdConfig.fetch(saleConfigPubkey);
+      assert.strictEqual(config.paymentMints.length, 1);
+      assert.isTrue(config.paymentMints[0].mint.equals(usdcMint.publicKey));
+    });
+
+    it("moves the payment into the treasury token account", async () => {
+      const quote = usdcPrice.muln(4);
+      await expectError(mintWithUsdc(4, quote.subn(1)), "PriceExceedsLimit");
+      await mintWithUsdc(4, quote);
+
+      const treasuryBalance = await provider.connection.getTokenAccountBalance(treasuryUsdcAccount);
+      assert.strictEqual(treasuryBalance.value.amount, quote.toString());
+      const buyerBalance = await provider.connection.getTokenAccountBalance(buyerUsdcAccount);
+      assert.strictEqual(buyerBalance.value.amount, new BN(100_000_000).sub(quote).toString());
+
+      const config = await program.account.nsdConfig.fetch(saleConfigPubkey);
+      assert.strictEqual(config.totalMinted.toString(), "4");
+      assert.strictEqual(config.totalRevenue.toString(), "0");
+    });
+
+    it("lets the admin withdraw the stablecoin", async () => {
+      const withdraw = (amount: BN) =>
+        program.methods
+          .withdrawPaymentTokens(amount)
+          .accountsPartial({
+            feePayer: systemWallet.publicKey,
+            config: saleConfigPubkey,
+            admin: systemWallet.publicKey,
+            paymentMint: usdcMint.publicKey,
+            treasuryPaymentAccount: treasuryUsdcAccount,
+            destination: buyerUsdcAccount,
+            paymentTokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      const collected = usdcPrice.muln(4);
+      await expectError(withdraw(collected.addn(1)), "InsufficientTreasuryBalance");
+      await withdraw(collected);
+
+      const treasuryBalance = await provider.connection.getTokenAccountBalance(treasuryUsdcAccount);
+      assert.strictEqual(treasuryBalance.value.amount, "0");
+      const buyerBalance = await provider.connection.getTokenAccountBalance(buyerUsdcAccount);
+      assert.strictEqual(buyerBalance.value.amount, "100000000");
+    });
+  });
+
+  describe("token-2022 sales", () => {
+    const mint2022 = web3.Keypair.generate();
+    const [config2022Pubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nsd_config"), mint2022.publicKey.toBuffer()],
+      program.programId,
+    );
+    const userTokenAccount = web3.PublicKey.findProgramAddressSync(
+      [systemWallet.publicKey.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), mint2022.publicKey.toBuffer()],
+      anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    )[0];
+
+    before(async () => {
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(500), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: mint2022.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([mint2022])
+        .rpc();
+    });
+
+    it("creates the mint under the Token-2022 program and mints from it", async () => {
+      const mintInfo = await provider.connection.getAccountInfo(mint2022.publicKey);
+      assert.isTrue(mintInfo?.owner.equals(TOKEN_2022_PROGRAM_ID));
+
+      await program.methods
+        .mintTokensV2(new BN(3), U64_MAX)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: config2022Pubkey,
+          tokenMint: mint2022.publicKey,
+          user: systemWallet.publicKey,
+          userTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          salePhase: null,
+        })
+        .rpc();
+
+      const balance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+      assert.strictEqual(balance.value.amount, "3");
+    });
+
+    it("stores metadata in the mint without Metaplex accounts", async () => {
+      await program.methods
+        .setTokenMetadata(mint2022.publicKey, "NSD 2022", "NSD22", "https://example.com/nsd-2022.json")
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: config2022Pubkey,
+          metadata: null,
+          admin: systemWallet.publicKey,
+          mint: mint2022.publicKey,
+          metaplexMetadata: null,
+          tokenMetadataProgram: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      let mintInfo = await provider.connection.getAccountInfo(mint2022.publicKey);
+      assert.isTrue(mintInfo?.data.includes(Buffer.from("NSD 2022")));
+      assert.isTrue(mintInfo?.data.includes(Buffer.from("https://example.com/nsd-2022.json")));
+
+      await program.methods
+        .updateTokenMetadata("NSD Token 2022", null, null)
+        .accountsPartial({
+          feePayer: systemWallet.publicKey,
+          config: config2022Pubkey,
+          metadata: null,
+          admin: systemWallet.publicKey,
+          mint: mint2022.publicKey,
+          metaplexMetadata: null,
+          tokenMetadataProgram: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      mintInfo = await provider.connection.getAccountInfo(mint2022.publicKey);
+      assert.isTrue(mintInfo?.data.includes(Buffer.from("NSD Token 2022")));
+
+      // The metadata account is never created next to a Token-2022 mint
+      const [metadataPubkey] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), mint2022.publicKey.toBuffer()],
+        program.programId,
+      );
+      assert.isNull(await provider.connection.getAccountInfo(metadataPubkey));
+    });
+
+    it("rejects an SPL Token mint without Metaplex accounts", async () => {
+      const splMint = web3.Keypair.generate();
+      const [splConfigPubkey] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("nsd_config"), splMint.publicKey.toBuffer()],
+        program.programId,
+      );
+      await program.methods
+        .initializeSale(0, new BN(100), new BN(500), null, null)
+        .accounts({
+          feePayer: systemWallet.publicKey,
+          admin: systemWallet.publicKey,
+          tokenMint: splMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([splMint])
+        .rpc();
 
-  it("First test", async () => {
-    // Add your test here
+      await expectError(
+        program.methods
+          .setTokenMetadata(splMint.publicKey, "NSD", "NSD", "https://example.com/nsd.json")
+          .accountsPartial({
+            feePayer: systemWallet.publicKey,
+            config: splConfigPubkey,
+            metadata: null,
+            admin: systemWallet.publicKey,
+            mint: splMint.publicKey,
+            metaplexMetadata: null,
+            tokenMetadataProgram: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc(),
+        "MissingMetadataAccounts",
+      );
+    });
   });
 });